@@ -0,0 +1,6 @@
+// Analyses that look at a whole run, beyond the per-process numbers in `metrics`.
+
+pub mod anomaly;
+pub mod bounds;
+pub mod fairness;
+pub mod timeseries;
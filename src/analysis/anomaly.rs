@@ -0,0 +1,335 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::{
+    log::TickEntry,
+    metrics::{self, scheduled_pid},
+    process::{Burst, BurstKind},
+    report::{markers, segments, Lane, MarkerKind},
+    scheduler::SchedulerResult,
+};
+
+// Looks for the classic ways a schedule goes wrong and says when and to whom.
+// The thresholds are deliberately simple, this is meant to point a person at the
+// interesting part of a run, not to be a verdict.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    // a CPU segment has to be at least this long to hold up a convoy.
+    pub convoy_burst: usize,
+    // and have at least this many short jobs stuck behind it.
+    pub convoy_waiters: usize,
+    // a higher priority process has to be kept waiting this many ticks in a row.
+    pub inversion_ticks: usize,
+    // this many preemptions within `thrash_window` ticks counts as thrashing.
+    pub thrash_window: usize,
+    pub thrash_preemptions: usize,
+    // the CPU has to sit idle this long with work queued for IO.
+    pub idle_gap: usize,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            convoy_burst: 8,
+            convoy_waiters: 2,
+            inversion_ticks: 3,
+            thrash_window: 10,
+            thrash_preemptions: 5,
+            idle_gap: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FindingKind {
+    Convoy,
+    PriorityInversion,
+    Thrashing,
+    IdleWithIoWork,
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FindingKind::Convoy => write!(f, "CONVOY"),
+            FindingKind::PriorityInversion => write!(f, "PRIORITY INVERSION"),
+            FindingKind::Thrashing => write!(f, "THRASHING"),
+            FindingKind::IdleWithIoWork => write!(f, "CPU IDLE WITH IO BACKLOG"),
+        }
+    }
+}
+
+/// `end` is exclusive, like `Segment`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub start: usize,
+    pub end: usize,
+    pub pids: Vec<i32>,
+    pub detail: String,
+}
+
+fn pid_list<'a>(pids: impl IntoIterator<Item = &'a i32>) -> String {
+    pids.into_iter().map(|pid| pid.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+fn convoys(content: &[TickEntry], thresholds: &Thresholds) -> Vec<Finding> {
+    let mut findings = vec![];
+    for segment in segments(content) {
+        let length = segment.end - segment.start;
+        if segment.lane != Lane::Cpu || length < thresholds.convoy_burst {
+            continue;
+        }
+        // short is "would have been done in less than half the time the CPU was held".
+        let stuck = content[segment.start..segment.end]
+            .iter()
+            .flat_map(|entry| entry.cpu_queue.iter())
+            .filter(|proc| proc.pid != segment.pid)
+            .filter(|proc| matches!(proc.burst.front(), Some(Burst(BurstKind::Cpu, len)) if (*len as usize) * 2 < length))
+            .map(|proc| proc.pid)
+            .collect::<BTreeSet<_>>();
+        let io_idle = content[segment.start..segment.end]
+            .iter()
+            .filter(|entry| scheduled_pid(&entry.io_process).is_none())
+            .count();
+        if stuck.len() >= thresholds.convoy_waiters && io_idle * 2 >= length {
+            findings.push(Finding {
+                kind: FindingKind::Convoy,
+                start: segment.start,
+                end: segment.end,
+                pids: std::iter::once(segment.pid).chain(stuck.iter().copied()).collect(),
+                detail: format!(
+                    "{} (pid {}) held the CPU for {} ticks while {} short jobs waited and IO sat idle for {} of them",
+                    segment.name,
+                    segment.pid,
+                    length,
+                    stuck.len(),
+                    io_idle,
+                ),
+            });
+        }
+    }
+    findings
+}
+
+fn priority_inversions(content: &[TickEntry], thresholds: &Thresholds) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = vec![];
+    // (start, running pid, pids of higher priority processes kept waiting)
+    let mut open: Option<(usize, i32, BTreeSet<i32>)> = None;
+    for (time, entry) in content.iter().enumerate() {
+        // lower numbers are higher priority, same as the Priority scheduler.
+        let inversion = match &entry.cpu_process {
+            SchedulerResult::Finished(running) | SchedulerResult::Processing(running) => {
+                let waiting = entry
+                    .cpu_queue
+                    .iter()
+                    .filter(|proc| proc.pid != running.pid && proc.priority < running.priority)
+                    .map(|proc| proc.pid)
+                    .collect::<BTreeSet<_>>();
+                (!waiting.is_empty()).then_some((running.pid, waiting))
+            }
+            _ => None,
+        };
+        match (&mut open, inversion) {
+            (Some((_, open_pid, waiting)), Some((pid, more))) if *open_pid == pid => waiting.extend(more),
+            (_, inversion) => {
+                if let Some((start, pid, waiting)) = open.take() {
+                    findings.extend(inversion_finding(start, time, pid, waiting, thresholds));
+                }
+                open = inversion.map(|(pid, waiting)| (time, pid, waiting));
+            }
+        }
+    }
+    if let Some((start, pid, waiting)) = open {
+        findings.extend(inversion_finding(start, content.len(), pid, waiting, thresholds));
+    }
+    findings
+}
+
+fn inversion_finding(start: usize, end: usize, pid: i32, waiting: BTreeSet<i32>, thresholds: &Thresholds) -> Option<Finding> {
+    if end - start < thresholds.inversion_ticks {
+        return None;
+    }
+    Some(Finding {
+        kind: FindingKind::PriorityInversion,
+        start,
+        end,
+        pids: std::iter::once(pid).chain(waiting.iter().copied()).collect(),
+        detail: format!("pid {} ran for {} ticks while higher priority pids {} were ready", pid, end - start, pid_list(&waiting)),
+    })
+}
+
+fn thrashing(content: &[TickEntry], thresholds: &Thresholds) -> Vec<Finding> {
+    let preemptions = markers(content)
+        .into_iter()
+        .filter(|marker| marker.kind == MarkerKind::Preemption)
+        .collect::<Vec<_>>();
+    let mut findings: Vec<Finding> = vec![];
+    for (i, first) in preemptions.iter().enumerate() {
+        let in_window = preemptions[i..]
+            .iter()
+            .take_while(|marker| marker.time < first.time + thresholds.thrash_window)
+            .collect::<Vec<_>>();
+        if in_window.len() < thresholds.thrash_preemptions {
+            continue;
+        }
+        // exclusive, like every other finding.
+        let end = in_window.last().unwrap().time + 1;
+        let pids = in_window.iter().map(|marker| marker.pid).collect::<BTreeSet<_>>();
+        // overlapping windows are one long bout of thrashing.
+        match findings.last_mut() {
+            Some(last) if first.time < last.end => {
+                last.end = last.end.max(end);
+                last.pids = last.pids.iter().copied().chain(pids).collect::<BTreeSet<_>>().into_iter().collect();
+            }
+            _ => findings.push(Finding {
+                kind: FindingKind::Thrashing,
+                start: first.time,
+                end,
+                pids: pids.into_iter().collect(),
+                detail: String::new(),
+            }),
+        }
+    }
+    for finding in &mut findings {
+        let count = preemptions.iter().filter(|m| (finding.start..finding.end).contains(&m.time)).count();
+        finding.detail = format!("{} preemptions in {} ticks between pids {}", count, finding.end - finding.start, pid_list(&finding.pids));
+    }
+    findings
+}
+
+fn idle_with_io_work(content: &[TickEntry], thresholds: &Thresholds) -> Vec<Finding> {
+    let mut findings = vec![];
+    let mut start = None;
+    let mut pids = BTreeSet::new();
+    for (time, entry) in content.iter().enumerate().chain(std::iter::once((content.len(), &content[0]))) {
+        let io_running = scheduled_pid(&entry.io_process);
+        let backlog = entry.io_queue.iter().filter(|proc| Some(proc.pid) != io_running).map(|proc| proc.pid).collect::<Vec<_>>();
+        let idle = time < content.len() && scheduled_pid(&entry.cpu_process).is_none() && !backlog.is_empty();
+        match (idle, start) {
+            (true, None) => {
+                start = Some(time);
+                pids.extend(backlog);
+            }
+            (true, Some(_)) => pids.extend(backlog),
+            (false, Some(from)) => {
+                if time - from >= thresholds.idle_gap {
+                    findings.push(Finding {
+                        kind: FindingKind::IdleWithIoWork,
+                        start: from,
+                        end: time,
+                        pids: pids.iter().copied().collect(),
+                        detail: format!("CPU idle for {} ticks while pids {} queued for IO", time - from, pid_list(&pids)),
+                    });
+                }
+                start = None;
+                pids.clear();
+            }
+            (false, None) => {}
+        }
+    }
+    findings
+}
+
+pub fn analyze(content: &[TickEntry], thresholds: &Thresholds) -> Vec<Finding> {
+    let mut findings = convoys(content, thresholds);
+    findings.extend(priority_inversions(content, thresholds));
+    findings.extend(thrashing(content, thresholds));
+    findings.extend(idle_with_io_work(content, thresholds));
+    findings.sort_by_key(|finding| (finding.start, finding.kind));
+    findings
+}
+
+pub fn report(content: &[TickEntry], thresholds: &Thresholds) -> String {
+    let findings = analyze(content, thresholds);
+    if findings.is_empty() {
+        return "No anomalies found.\n".to_string();
+    }
+    let names = metrics::processes(content);
+    let mut out = format!("{} findings\n\n", findings.len());
+    for finding in findings {
+        let involved = finding
+            .pids
+            .iter()
+            .map(|pid| match names.iter().find(|proc| proc.pid == *pid) {
+                Some(proc) => format!("{} ({})", proc.name, pid),
+                None => pid.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push_str(&format!(
+            "T{}-T{}: {}\n    {}\n    involved: {}\n",
+            finding.start, finding.end, finding.kind, finding.detail, involved
+        ));
+    }
+    out
+}
+
+pub fn write_report(content: &[TickEntry], out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "{}", report(content, &Thresholds::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::Simulation, process::Process, scheduler::Policy};
+
+    fn run(processes: Vec<Process>, policy: Policy) -> Vec<Finding> {
+        let log = Simulation::with_policy(processes, policy).run();
+        analyze(&log.content, &Thresholds::default())
+    }
+
+    #[test]
+    fn test_convoy() {
+        let processes = vec![
+            Process::new("long".to_string(), 0, 0, vec![Burst(BurstKind::Cpu, 20)], 0),
+            Process::new("a".to_string(), 1, 0, vec![Burst(BurstKind::Cpu, 1), Burst(BurstKind::Io, 5)], 1),
+            Process::new("b".to_string(), 2, 0, vec![Burst(BurstKind::Cpu, 1), Burst(BurstKind::Io, 5)], 1),
+        ];
+        let findings = run(processes.clone(), Policy::Fcfs);
+        assert!(findings.iter().any(|f| f.kind == FindingKind::Convoy && f.pids == vec![0, 1, 2]));
+        // round robin lets the short jobs through.
+        let findings = run(processes, Policy::RoundRobin { quantum: 2 });
+        assert!(findings.iter().all(|f| f.kind != FindingKind::Convoy));
+    }
+
+    #[test]
+    fn test_priority_inversion() {
+        let processes = vec![
+            Process::new("low".to_string(), 0, 5, vec![Burst(BurstKind::Cpu, 6)], 0),
+            Process::new("high".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 2)], 1),
+        ];
+        let findings = run(processes.clone(), Policy::Fcfs);
+        assert!(findings.iter().any(|f| f.kind == FindingKind::PriorityInversion && f.start == 1 && f.end == 6));
+        assert!(run(processes, Policy::Priority).is_empty());
+    }
+
+    #[test]
+    fn test_thrashing() {
+        let cpu = |pid: i32, len| Process::new(format!("P{}", pid), pid, 0, vec![Burst(BurstKind::Cpu, len)], 0);
+        // a preemption every tick for 18 ticks is one bout, not one per overlapping window.
+        let findings = run(vec![cpu(0, 10), cpu(1, 10)], Policy::RoundRobin { quantum: 1 });
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].kind, findings[0].start, findings[0].end), (FindingKind::Thrashing, 1, 19));
+        assert_eq!(findings[0].detail, "18 preemptions in 18 ticks between pids 0, 1");
+        // it stops once the short ones are out of the way and the long one has the CPU to itself.
+        let findings = run(vec![cpu(0, 3), cpu(1, 3), cpu(2, 20)], Policy::RoundRobin { quantum: 1 });
+        assert_eq!((findings[0].start, findings[0].end, findings[0].pids.clone()), (1, 7, vec![0, 1, 2]));
+        // a preemption every 4 ticks is never 5 in 10.
+        assert!(run(vec![cpu(0, 10), cpu(1, 10)], Policy::RoundRobin { quantum: 4 }).is_empty());
+    }
+
+    #[test]
+    fn test_idle_with_io_work() {
+        let io = |pid: i32| Process::new(format!("P{}", pid), pid, 0, vec![Burst(BurstKind::Cpu, 1), Burst(BurstKind::Io, 5)], 0);
+        // both are done with the CPU by 2, P1 then waits on P0's IO with the CPU doing nothing.
+        let findings = run(vec![io(0), io(1)], Policy::Fcfs);
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].kind, findings[0].start, findings[0].end), (FindingKind::IdleWithIoWork, 2, 6));
+        assert_eq!(findings[0].pids, vec![1]);
+        // the CPU is idle just the same with one, but nothing is queued behind the IO.
+        assert!(run(vec![io(0)], Policy::Fcfs).is_empty());
+    }
+}
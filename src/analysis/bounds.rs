@@ -0,0 +1,133 @@
+use crate::process::{BurstKind, Process};
+
+// Numbers no policy can beat for a workload, so a comparison has something to be
+// measured against. They only look at the workload, never at a run.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    // what shortest-remaining-time-first gets with IO taken out of the picture.
+    pub avg_wait: f64,
+    pub avg_turnaround: f64,
+    pub makespan: i32,
+}
+
+/// What each bound assumes, in the order `Bounds` has them. Printed with the bounds
+/// since a bound is only as useful as knowing when it can't be reached.
+pub const ASSUMPTIONS: [(&str, &str); 3] = [
+    (
+        "avg wait",
+        "SRTF on one CPU with every process's CPU bursts run back to back and IO taking no time. \
+         Optimal for CPU-only workloads; with IO it is a reference point, not a guarantee.",
+    ),
+    (
+        "avg turnaround",
+        "the larger of the SRTF turnaround above and every process running all its bursts without \
+         ever waiting. Holds with IO, since one CPU can't beat SRTF and no process beats its own bursts.",
+    ),
+    (
+        "makespan",
+        "the largest of the critical path (arrival + all bursts) of any process and the CPU and IO \
+         work that arrives at or after any arrival time, each device doing one thing at a time.",
+    ),
+];
+
+fn work(proc: &Process, kind: BurstKind) -> i32 {
+    proc.burst.iter().filter(|burst| burst.0 == kind).map(|burst| burst.1).sum()
+}
+
+// completion time of each `(arrival, work)` job under preemptive SRTF, a tick at a time
+// like the simulation so a process can start in the tick it arrives.
+fn srtf_completions(jobs: &[(i32, i32)]) -> Vec<i32> {
+    let mut remaining = jobs.iter().map(|job| job.1).collect::<Vec<_>>();
+    let mut completion = jobs.iter().map(|job| job.0).collect::<Vec<_>>();
+    let mut time = jobs.iter().map(|job| job.0).min().unwrap_or(0);
+    while remaining.iter().any(|&left| left > 0) {
+        let next = (0..jobs.len())
+            .filter(|&i| jobs[i].0 <= time && remaining[i] > 0)
+            .min_by_key(|&i| (remaining[i], jobs[i].0));
+        time += 1;
+        if let Some(i) = next {
+            remaining[i] -= 1;
+            if remaining[i] == 0 {
+                completion[i] = time;
+            }
+        }
+    }
+    completion
+}
+
+pub fn bounds(processes: &[Process]) -> Bounds {
+    if processes.is_empty() {
+        return Bounds { avg_wait: 0., avg_turnaround: 0., makespan: 0 };
+    }
+    let n = processes.len() as f64;
+    let jobs = processes.iter().map(|proc| (proc.arrival, work(proc, BurstKind::Cpu))).collect::<Vec<_>>();
+    let srtf_turnaround = srtf_completions(&jobs)
+        .iter()
+        .zip(&jobs)
+        .map(|(completion, (arrival, _))| completion - arrival)
+        .collect::<Vec<_>>();
+    let srtf_wait = srtf_turnaround.iter().zip(&jobs).map(|(turnaround, (_, cpu))| turnaround - cpu).sum::<i32>();
+    let no_waiting = processes.iter().map(|proc| proc.burst.iter().map(|burst| burst.1).sum::<i32>()).sum::<i32>();
+
+    let critical_path = processes
+        .iter()
+        .map(|proc| proc.arrival + proc.burst.iter().map(|burst| burst.1).sum::<i32>())
+        .max()
+        .unwrap();
+    // nothing that arrives at `from` or later can be worked on before `from`.
+    let device = |kind: BurstKind| {
+        processes
+            .iter()
+            .map(|from| {
+                from.arrival
+                    + processes
+                        .iter()
+                        .filter(|proc| proc.arrival >= from.arrival)
+                        .map(|proc| work(proc, kind))
+                        .sum::<i32>()
+            })
+            .max()
+            .unwrap()
+    };
+
+    Bounds {
+        avg_wait: srtf_wait as f64 / n,
+        avg_turnaround: srtf_turnaround.iter().sum::<i32>().max(no_waiting) as f64 / n,
+        makespan: critical_path.max(device(BurstKind::Cpu)).max(device(BurstKind::Io)),
+    }
+}
+
+/// How far `value` is above `bound`, as a fraction of the bound. 0 when both are 0.
+pub fn gap(value: f64, bound: f64) -> f64 {
+    if bound == 0. {
+        if value == 0. { 0. } else { f64::INFINITY }
+    } else {
+        (value - bound) / bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compare, process::Burst, scheduler::Policy};
+
+    #[test]
+    fn test_bounds() {
+        let processes = (0..3)
+            .zip([24, 3, 3])
+            .map(|(pid, burst)| Process::new(format!("P{}", pid + 1), pid, 0, vec![Burst(BurstKind::Cpu, burst)], 0))
+            .collect::<Vec<_>>();
+        let bounds = bounds(&processes);
+        // SRTF runs 3, 3, 24: waits 0, 3, 6.
+        assert_eq!(bounds, Bounds { avg_wait: 3., avg_turnaround: 13., makespan: 30 });
+
+        // nothing may do better than the bound, and FCFS with the long job first is well off it.
+        for result in compare::compare(&processes, &compare::all_policies(&[1, 4])) {
+            assert!(result.summary.avg_wait >= bounds.avg_wait);
+            assert!(result.makespan >= bounds.makespan);
+        }
+        let fcfs = &compare::compare(&processes, &[Policy::Fcfs])[0];
+        assert_eq!(gap(fcfs.summary.avg_wait, bounds.avg_wait), (17. - 3.) / 3.);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::{log::TickEntry, metrics};
+
+pub const DEFAULT_STARVATION_THRESHOLD: i32 = 50;
+
+/// Pids sitting in a queue during the tick without getting either device.
+pub fn waiting_pids(entry: &TickEntry) -> HashSet<i32> {
+    let running = [&entry.cpu_process, &entry.io_process]
+        .into_iter()
+        .filter_map(metrics::scheduled_pid)
+        .collect::<HashSet<_>>();
+    entry
+        .cpu_queue
+        .iter()
+        .chain(entry.io_queue.iter())
+        .map(|proc| proc.pid)
+        .filter(|pid| !running.contains(pid))
+        .collect()
+}
+
+/// The longest stretch of consecutive ticks each process spent waiting.
+pub fn max_continuous_waits(content: &[TickEntry]) -> HashMap<i32, i32> {
+    let mut longest = HashMap::new();
+    let mut current: HashMap<i32, i32> = HashMap::new();
+    for entry in content {
+        let waiting = waiting_pids(entry);
+        current.retain(|pid, _| waiting.contains(pid));
+        for pid in waiting {
+            let streak = current.entry(pid).or_insert(0);
+            *streak += 1;
+            let best = longest.entry(pid).or_insert(0);
+            *best = (*best).max(*streak);
+        }
+    }
+    longest
+}
+
+/// Jain's fairness index, 1 when everyone got the same and 1/n when one got everything.
+pub fn jain_index(values: &[f64]) -> f64 {
+    let sum = values.iter().sum::<f64>();
+    let sum_of_squares = values.iter().map(|v| v * v).sum::<f64>();
+    if sum_of_squares == 0. {
+        return 1.;
+    }
+    sum * sum / (values.len() as f64 * sum_of_squares)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessFairness {
+    pub pid: i32,
+    pub name: String,
+    // fraction of its time in the system the process spent on the CPU.
+    pub cpu_share: f64,
+    pub max_continuous_wait: i32,
+    // turnaround / service time, only known once the process finished.
+    pub normalized_turnaround: Option<f64>,
+    pub starved: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Fairness {
+    pub jain_index: f64,
+    pub starvation_threshold: i32,
+    pub starved: usize,
+    pub processes: Vec<ProcessFairness>,
+}
+
+pub fn fairness(content: &[TickEntry], starvation_threshold: i32) -> Fairness {
+    let waits = max_continuous_waits(content);
+    let mut processes = metrics::processes(content)
+        .into_iter()
+        // processes that haven't shown up yet have no share to speak of.
+        .filter(|proc| (proc.arrival as usize) < content.len())
+        .map(|proc| {
+            let cpu_time = metrics::cpu_time(proc.pid, content);
+            let service = cpu_time + metrics::io_time(proc.pid, content);
+            let end = metrics::completion_time(proc.pid, content).unwrap_or(content.len() as i32);
+            let turnaround = metrics::turn_around_time(proc.pid, content);
+            let max_continuous_wait = waits.get(&proc.pid).copied().unwrap_or(0);
+            ProcessFairness {
+                pid: proc.pid,
+                name: proc.name,
+                cpu_share: cpu_time as f64 / (end - proc.arrival).max(1) as f64,
+                max_continuous_wait,
+                normalized_turnaround: turnaround.filter(|_| service > 0).map(|t| t as f64 / service as f64),
+                starved: max_continuous_wait >= starvation_threshold,
+            }
+        })
+        .collect::<Vec<_>>();
+    processes.sort_by_key(|proc| proc.pid);
+    Fairness {
+        jain_index: jain_index(&processes.iter().map(|proc| proc.cpu_share).collect::<Vec<_>>()),
+        starvation_threshold,
+        starved: processes.iter().filter(|proc| proc.starved).count(),
+        processes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        log::{Log, LogEventKind},
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    // FCFS runs A 0-4, B 4-6 and C 6-7, C having arrived at 1.
+    fn log(starvation_threshold: i32) -> Log {
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 4)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 2)], 0),
+            Process::new("C".to_string(), 2, 1, vec![Burst(BurstKind::Cpu, 1)], 1),
+        ];
+        let mut sim = Simulation::with_policy(processes, Policy::Fcfs);
+        sim.log.starvation_threshold = starvation_threshold;
+        sim.run()
+    }
+
+    #[test]
+    fn test_max_continuous_waits() {
+        // A never waits so it isn't in there at all.
+        assert_eq!(max_continuous_waits(&log(5).content), HashMap::from([(1, 4), (2, 5)]));
+
+        // round robin breaks the waits up: A and B take turns, so neither waits more than a tick in a row.
+        let processes = (0..2).map(|pid| Process::new(format!("P{}", pid), pid, 1, vec![Burst(BurstKind::Cpu, 3)], 0)).collect();
+        let log = Simulation::with_policy(processes, Policy::RoundRobin { quantum: 1 }).run();
+        assert_eq!(max_continuous_waits(&log.content), HashMap::from([(0, 1), (1, 1)]));
+    }
+
+    #[test]
+    fn test_fairness() {
+        let log = log(5);
+        let fairness = fairness(&log.content, log.starvation_threshold);
+        let per_process = |f: fn(&ProcessFairness) -> String| fairness.processes.iter().map(f).collect::<Vec<_>>();
+        // turnaround over time spent running: 4 / 4, 6 / 2 and 6 / 1.
+        assert_eq!(
+            fairness.processes.iter().map(|proc| proc.normalized_turnaround).collect::<Vec<_>>(),
+            vec![Some(1.), Some(3.), Some(6.)]
+        );
+        assert_eq!(per_process(|proc| format!("{}:{}", proc.name, proc.starved)), ["A:false", "B:false", "C:true"]);
+        assert_eq!(fairness.starved, 1);
+
+        // nothing's normalized turnaround is known before it finishes.
+        let partway = super::fairness(&log.content[..5], 5);
+        assert_eq!(partway.processes[0].normalized_turnaround, Some(1.));
+        assert_eq!(partway.processes[1].normalized_turnaround, None);
+        assert_eq!(partway.starved, 0);
+    }
+
+    #[test]
+    fn test_starvation_event() {
+        let starvation = |log: &Log| {
+            let events = log.events();
+            events
+                .events()
+                .iter()
+                .filter(|event| event.kind == LogEventKind::Starvation)
+                .map(|event| event.to_string())
+                .collect::<Vec<_>>()
+        };
+        // the warning comes once, on the tick the threshold is reached.
+        assert_eq!(starvation(&log(5)), ["T5: WARNING: C HAS WAITED 5 TICKS IN A ROW (STARVATION)"]);
+        assert_eq!(
+            starvation(&log(4)),
+            ["T3: WARNING: B HAS WAITED 4 TICKS IN A ROW (STARVATION)", "T4: WARNING: C HAS WAITED 4 TICKS IN A ROW (STARVATION)"]
+        );
+        assert!(starvation(&log(6)).is_empty());
+    }
+
+    #[test]
+    fn test_jain_index() {
+        assert_eq!(jain_index(&[1., 1., 1., 1.]), 1.);
+        assert_eq!(jain_index(&[1., 0., 0., 0.]), 0.25);
+        assert!((jain_index(&[1., 2.]) - 0.9).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::{log::TickEntry, metrics::scheduled_pid, process::Process, report::csv, scheduler::SchedulerResult};
+
+pub const DEFAULT_WINDOW: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Sample {
+    pub time: usize,
+    pub cpu_busy: bool,
+    pub io_busy: bool,
+    // utilization over the `window` ticks ending at (and including) `time`.
+    pub cpu_utilization: f64,
+    pub io_utilization: f64,
+    // processes waiting for a device, not counting the one using it.
+    pub ready_queue: usize,
+    pub io_queue: usize,
+    // processes that want the CPU, including the one on it.
+    pub runnable: usize,
+}
+
+pub fn time_series(content: &[TickEntry], window: usize) -> Vec<Sample> {
+    let busy = |device: fn(&TickEntry) -> &SchedulerResult| {
+        content.iter().map(|entry| scheduled_pid(device(entry)).is_some()).collect::<Vec<_>>()
+    };
+    let cpu_busy = busy(|entry| &entry.cpu_process);
+    let io_busy = busy(|entry| &entry.io_process);
+    let utilization = |busy: &[bool], time: usize| {
+        let from = (time + 1).saturating_sub(window);
+        busy[from..=time].iter().filter(|&&busy| busy).count() as f64 / (time + 1 - from) as f64
+    };
+
+    content
+        .iter()
+        .enumerate()
+        .map(|(time, entry)| {
+            let waiting = |queue: &[Process], running: Option<i32>| {
+                queue.iter().filter(|proc| Some(proc.pid) != running).count()
+            };
+            Sample {
+                time,
+                cpu_busy: cpu_busy[time],
+                io_busy: io_busy[time],
+                cpu_utilization: utilization(&cpu_busy, time),
+                io_utilization: utilization(&io_busy, time),
+                ready_queue: waiting(&entry.cpu_queue, scheduled_pid(&entry.cpu_process)),
+                io_queue: waiting(&entry.io_queue, scheduled_pid(&entry.io_process)),
+                runnable: entry.cpu_queue.len(),
+            }
+        })
+        .collect()
+}
+
+pub fn write_csv(content: &[TickEntry], window: usize, out: &mut dyn Write) -> io::Result<()> {
+    let header = [
+        "time", "cpu_busy", "io_busy", "cpu_utilization", "io_utilization",
+        "ready_queue", "io_queue", "runnable",
+    ];
+    csv::write_row(out, &header.map(String::from))?;
+    for sample in time_series(content, window) {
+        csv::write_row(
+            out,
+            &[
+                sample.time.to_string(),
+                (sample.cpu_busy as u8).to_string(),
+                (sample.io_busy as u8).to_string(),
+                sample.cpu_utilization.to_string(),
+                sample.io_utilization.to_string(),
+                sample.ready_queue.to_string(),
+                sample.io_queue.to_string(),
+                sample.runnable.to_string(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        metrics,
+        process::{Burst, BurstKind},
+        scheduler::Policy,
+    };
+
+    #[test]
+    fn test_time_series() {
+        // FCFS: A runs 0-2, does IO 2-4 and runs again at 4, B runs 2-4 in between.
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 2)], 0),
+        ];
+        let log = Simulation::with_policy(processes, Policy::Fcfs).run();
+        let samples = time_series(&log.content, 2);
+        assert_eq!(samples.len(), 5);
+        let column = |f: fn(&Sample) -> f64| samples.iter().map(f).collect::<Vec<_>>();
+        assert_eq!(column(|s| s.cpu_utilization), [1., 1., 1., 1., 1.]);
+        // the first tick's window is only itself.
+        assert_eq!(column(|s| s.io_utilization), [0., 0., 0.5, 1., 0.5]);
+        // B waits while A runs, A waits for IO the tick it's handed over, then for the CPU once it's done.
+        assert_eq!(column(|s| s.ready_queue as f64), [1., 1., 0., 1., 0.]);
+        assert_eq!(column(|s| s.io_queue as f64), [0., 1., 0., 0., 0.]);
+        assert_eq!(column(|s| s.runnable as f64), [2., 1., 1., 1., 0.]);
+
+        // a window longer than the run only averages over the ticks there are.
+        let samples = time_series(&log.content, 10);
+        assert_eq!(samples.iter().map(|s| s.io_utilization).collect::<Vec<_>>(), [0., 0., 1. / 3., 0.5, 0.4]);
+        assert_eq!(metrics::throughput(&log.content), 0.4);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    compare::{self, Comparison},
+    generate::{self, Rng},
+    scheduler::Policy,
+};
+
+// Runs policies over many generated workloads so a difference between them can be
+// told apart from the luck of one particular workload.
+
+// two-sided 95% critical values of Student's t by degrees of freedom. Anything between
+// two rows uses the smaller df, which errs on the side of a wider interval.
+const T_TABLE: [(usize, f64); 34] = [
+    (1, 12.706), (2, 4.303), (3, 3.182), (4, 2.776), (5, 2.571), (6, 2.447), (7, 2.365),
+    (8, 2.306), (9, 2.262), (10, 2.228), (11, 2.201), (12, 2.179), (13, 2.160), (14, 2.145),
+    (15, 2.131), (16, 2.120), (17, 2.110), (18, 2.101), (19, 2.093), (20, 2.086), (21, 2.080),
+    (22, 2.074), (23, 2.069), (24, 2.064), (25, 2.060), (26, 2.056), (27, 2.052), (28, 2.048),
+    (29, 2.045), (30, 2.042), (40, 2.021), (60, 2.000), (120, 1.980), (1000, 1.962),
+];
+
+pub fn t_critical(df: usize) -> f64 {
+    T_TABLE.iter().rev().find(|(row, _)| *row <= df).map(|(_, t)| *t).unwrap_or(f64::INFINITY)
+}
+
+/// A mean and its 95% confidence interval, `mean ± half_width`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub half_width: f64,
+}
+
+impl Estimate {
+    pub fn of(values: &[f64]) -> Self {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.)).sqrt();
+        Self {
+            mean,
+            std_dev,
+            half_width: t_critical(values.len().saturating_sub(1)) * std_dev / n.sqrt(),
+        }
+    }
+}
+
+/// A metric's column name and how to get it out of one run.
+pub type Metric = (&'static str, fn(&Comparison) -> f64);
+
+/// Every metric a batch reports.
+pub const METRICS: [Metric; 7] = [
+    ("AVG WAIT", |c| c.summary.avg_wait),
+    ("AVG TURNAROUND", |c| c.summary.avg_turnaround),
+    ("AVG RESPONSE", |c| c.summary.avg_response),
+    ("THROUGHPUT", |c| c.summary.throughput),
+    ("CPU", |c| c.summary.cpu_usage),
+    ("MAX WAIT", |c| c.max_wait as f64),
+    ("FAIRNESS", |c| c.jain_index),
+];
+
+/// Runs every policy on `runs` workloads, the k-th generated from `seed + k` so the
+/// policies see the same workloads. Indexed by policy, then run.
+pub fn run(policies: &[Policy], runs: usize, seed: u64, config: &generate::Config) -> Vec<Vec<Comparison>> {
+    let mut results = vec![vec![]; policies.len()];
+    for k in 0..runs {
+        let processes = generate::workload(config, &mut Rng::new(seed.wrapping_add(k as u64)));
+        for (i, result) in compare::compare(&processes, policies).into_iter().enumerate() {
+            results[i].push(result);
+        }
+    }
+    results
+}
+
+/// A paired t-test on `b - a`, run by run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairedTest {
+    pub diff: Estimate,
+    pub t: f64,
+    // at the 5% level, two-sided.
+    pub significant: bool,
+}
+
+pub fn paired_t_test(a: &[f64], b: &[f64]) -> PairedTest {
+    let diffs = a.iter().zip(b).map(|(a, b)| b - a).collect::<Vec<_>>();
+    let diff = Estimate::of(&diffs);
+    let t = if diff.std_dev == 0. {
+        // every run moved by exactly the same amount, which is either nothing or a sure thing.
+        if diff.mean == 0. { 0. } else { diff.mean.signum() * f64::INFINITY }
+    } else {
+        diff.mean / (diff.std_dev / (diffs.len() as f64).sqrt())
+    };
+    PairedTest { diff, t, significant: t.abs() > t_critical(diffs.len() - 1) }
+}
+
+fn values(runs: &[Comparison], metric: fn(&Comparison) -> f64) -> Vec<f64> {
+    runs.iter().map(metric).collect()
+}
+
+/// Mean ± 95% CI of every metric per policy, then every other policy tested against the first.
+pub fn report(policies: &[Policy], results: &[Vec<Comparison>]) -> String {
+    let runs = results[0].len();
+    let mut out = format!("{} runs per policy, mean ± 95% confidence interval\n\n{:<12}", runs, "POLICY");
+    for (name, _) in METRICS {
+        out.push_str(&format!(" {:>16}", name));
+    }
+    out.push('\n');
+    for (policy, runs) in policies.iter().zip(results) {
+        out.push_str(&format!("{:<12}", policy.to_string()));
+        for (_, metric) in METRICS {
+            let estimate = Estimate::of(&values(runs, metric));
+            out.push_str(&format!(" {:>16}", format!("{:.3}±{:.3}", estimate.mean, estimate.half_width)));
+        }
+        out.push('\n');
+    }
+
+    if policies.len() < 2 {
+        return out;
+    }
+    out.push_str(&format!(
+        "\nagainst {} (paired t-test, {} df, 5% level)\n{:<12} {:<16} {:>16} {:>8}  SIGNIFICANT\n",
+        policies[0],
+        runs - 1,
+        "POLICY",
+        "METRIC",
+        "DIFFERENCE",
+        "t"
+    ));
+    for (policy, runs) in policies.iter().zip(results).skip(1) {
+        for (name, metric) in METRICS {
+            let test = paired_t_test(&values(&results[0], metric), &values(runs, metric));
+            out.push_str(&format!(
+                "{:<12} {:<16} {:>16} {:>8.2}  {}\n",
+                policy.to_string(),
+                name,
+                format!("{:+.3}±{:.3}", test.diff.mean, test.diff.half_width),
+                test.t,
+                if test.significant { "yes" } else { "no" },
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate() {
+        let estimate = Estimate::of(&[2., 4., 4., 4., 5., 5., 7., 9.]);
+        assert_eq!(estimate.mean, 5.);
+        assert!((estimate.std_dev - 2.138).abs() < 1e-3);
+        // t(7) = 2.365
+        assert!((estimate.half_width - 2.365 * 2.138 / 8f64.sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_paired_t_test() {
+        let a = [10., 12., 9., 11., 10.];
+        let b = [12., 13., 11., 14., 12.];
+        let test = paired_t_test(&a, &b);
+        assert_eq!(test.diff.mean, 2.);
+        assert!(test.significant);
+        assert!(!paired_t_test(&a, &[11., 11., 10., 10., 10.]).significant);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{analysis::{fairness, timeseries}, compare::Sweep, report::ExportKind, scheduler::Policy};
+
+pub const USAGE: &str = "\
+Usage: RustScheduleSystem <file> [options]
+       RustScheduleSystem --batch <runs> [options]
+       RustScheduleSystem --open <saved run> [options]
+
+Options:
+  --format <fmt>   how to read <file>: workload (default), trace-csv or proc-stat
+  --tick <len>     timestamp units per simulator tick for traces (default 1)
+  --export <kind>=<path>
+                   write the finished run to <path> (- for stdout) instead of asking,
+                   kind is one of text, json, jsonl, csv, gantt, svg, html, trace,
+                   timeseries, anomalies.
+                   Can be given more than once.
+  --scheduler <s>  run with <s> instead of asking: fcfs, priority or rr:<quantum>
+  --compare        run every scheduler on the workload and print a comparison table,
+                   along with the workload's lower bounds and each scheduler's gap to them
+  --quanta <list>  comma separated quanta round robin is run with by --compare
+                   (default 1,2,4,8)
+  --sweep <param>=<from>..<to>[:<step>]
+                   run the scheduler owning <param> once per value (both ends
+                   included) and print a CSV of metrics per value. <param> can be
+                   quantum. A comma separated list of values works too.
+  --batch <runs>   run on <runs> generated workloads instead of <file> and print the
+                   mean and 95% confidence interval of each metric. Runs --scheduler
+                   (and --versus) if given, otherwise every scheduler like --compare,
+                   and tests each against the first with a paired t-test
+  --seed <n>       seed of the first generated workload, run k uses <n> + k (default 1)
+  --procs <n>      processes per generated workload (default 10)
+  --versus <s>     the scheduler --batch tests --scheduler against. Without --batch,
+                   opens a viewer stepping through both schedulers' runs of <file>
+                   side by side instead
+  --starvation <ticks>
+                   warn about processes waiting this many ticks in a row (default 50)
+  --window <ticks> window for the windowed utilization in timeseries (default 10)
+  --live           simulate the run in the viewer as it's watched, so processes can be
+                   added, killed or reprioritized partway through, or rewound to an
+                   earlier tick and carried on from there, under another scheduler if need be
+  --no-tui         don't open the viewer after the run
+  --save <path>    write the whole run to <path>: the workload, scheduler, options, any
+                   --live changes and every tick, so it can be reopened with --open
+  --open <path>    view or --export a run saved with --save instead of simulating <file>.
+                   Its own --starvation and --window are used
+  --config <path>  JSON file with the viewer's theme (default or high-contrast), process
+                   colors by name, hidden panes and pane sizes
+  --help           show this message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    // the `name arrival priority burst...` format from the project description.
+    Workload,
+    TraceCsv,
+    ProcStat,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub kind: ExportKind,
+    pub path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    // always there unless it's a batch run.
+    pub file: Option<String>,
+    pub format: InputFormat,
+    pub tick: f64,
+    pub exports: Vec<Export>,
+    pub scheduler: Option<Policy>,
+    pub compare: bool,
+    pub quanta: Vec<i32>,
+    pub sweep: Option<Sweep>,
+    pub batch: Option<usize>,
+    pub seed: u64,
+    pub procs: usize,
+    pub versus: Option<Policy>,
+    pub starvation_threshold: i32,
+    pub window: usize,
+    pub tui: bool,
+    pub live: bool,
+    pub config: Option<String>,
+    pub save: Option<String>,
+    // a saved run to show instead of simulating `file`.
+    pub open: Option<String>,
+}
+
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut file = None;
+        let mut format = InputFormat::Workload;
+        let mut tick = 1.;
+        let mut exports = vec![];
+        let mut scheduler = None;
+        let mut compare = false;
+        let mut quanta = vec![1, 2, 4, 8];
+        let mut sweep = None;
+        let mut batch = None;
+        let mut seed = 1;
+        let mut procs = 10;
+        let mut versus = None;
+        let mut starvation_threshold = fairness::DEFAULT_STARVATION_THRESHOLD;
+        let mut window = timeseries::DEFAULT_WINDOW;
+        let mut tui = true;
+        let mut live = false;
+        let mut config = None;
+        let mut save = None;
+        let mut open = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                "--format" => {
+                    format = match value(&arg, args.next())?.as_str() {
+                        "workload" => InputFormat::Workload,
+                        "trace-csv" => InputFormat::TraceCsv,
+                        "proc-stat" => InputFormat::ProcStat,
+                        other => return Err(format!("unknown format {:?}", other)),
+                    }
+                }
+                "--tick" => tick = parse_value(&arg, args.next())?,
+                "--export" => {
+                    let v = value(&arg, args.next())?;
+                    let (kind, path) = v
+                        .split_once('=')
+                        .ok_or_else(|| format!("--export expects <kind>=<path>, got {:?}", v))?;
+                    exports.push(Export { kind: kind.parse()?, path: path.to_string() });
+                }
+                "--scheduler" => scheduler = Some(value(&arg, args.next())?.parse()?),
+                "--compare" => compare = true,
+                "--quanta" => {
+                    quanta = parse_list(&arg, args.next())?;
+                    // same check as rr:<quantum>, a quantum that never runs out is just FCFS.
+                    for &quantum in &quanta {
+                        Policy::with_parameter("quantum", quantum)?;
+                    }
+                }
+                "--sweep" => sweep = Some(value(&arg, args.next())?.parse()?),
+                "--batch" => batch = Some(parse_value(&arg, args.next())?),
+                "--seed" => seed = parse_value(&arg, args.next())?,
+                "--procs" => procs = parse_value(&arg, args.next())?,
+                "--versus" => versus = Some(value(&arg, args.next())?.parse()?),
+                "--starvation" => {
+                    starvation_threshold = parse_value(&arg, args.next())?;
+                    // every process has waited 0 ticks in a row, so nothing could be told apart.
+                    if starvation_threshold <= 0 {
+                        return Err(format!("bad value \"{}\" for --starvation, it needs to be at least 1", starvation_threshold));
+                    }
+                }
+                "--window" => {
+                    window = parse_value(&arg, args.next())?;
+                    // a window of no ticks has no utilization to average.
+                    if window == 0 {
+                        return Err("bad value \"0\" for --window, it needs to be at least 1".to_string());
+                    }
+                }
+                "--no-tui" => tui = false,
+                "--live" => live = true,
+                "--config" => config = Some(value(&arg, args.next())?),
+                "--save" => save = Some(value(&arg, args.next())?),
+                "--open" => open = Some(value(&arg, args.next())?),
+                _ if arg.starts_with("--") => return Err(format!("unknown option {}\n\n{}", arg, USAGE)),
+                _ if file.is_none() => file = Some(arg),
+                _ => return Err(format!("unexpected argument {:?}\n\n{}", arg, USAGE)),
+            }
+        }
+
+        let simulating = file.is_some() || batch.is_some() || compare || sweep.is_some() || scheduler.is_some() || versus.is_some() || live;
+        if open.is_some() && simulating {
+            return Err("--open shows a run that's already been simulated, it can't be used with <file>, --batch, \
+                        --compare, --sweep, --scheduler, --versus or --live"
+                .to_string());
+        }
+        match (batch, &file) {
+            (Some(runs), _) if runs < 2 => return Err("--batch needs at least 2 runs for a confidence interval".to_string()),
+            (Some(_), Some(_)) => return Err("--batch generates its own workloads, it can't be given a file".to_string()),
+            (None, None) if open.is_none() => return Err(format!("Please pass in a file name\n\n{}", USAGE)),
+            _ => {}
+        }
+        if live && !tui {
+            return Err("--live runs in the viewer, it can't be used with --no-tui".to_string());
+        }
+        if versus.is_some() && batch.is_none() && (live || !tui) {
+            return Err("--versus without --batch opens its own viewer, it can't be used with --live or --no-tui".to_string());
+        }
+        if versus.is_some() && scheduler.is_none() {
+            return Err("--versus needs a --scheduler to test against".to_string());
+        }
+
+        Ok(Self {
+            file,
+            format,
+            tick,
+            exports,
+            scheduler,
+            compare,
+            quanta,
+            sweep,
+            batch,
+            seed,
+            procs,
+            versus,
+            starvation_threshold,
+            window,
+            tui,
+            live,
+            config,
+            save,
+            open,
+        })
+    }
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, v: Option<String>) -> Result<T, String> {
+    let v = value(flag, v)?;
+    v.parse().map_err(|_| format!("bad value {:?} for {}", v, flag))
+}
+
+fn parse_list<T: std::str::FromStr>(flag: &str, v: Option<String>) -> Result<Vec<T>, String> {
+    let v = value(flag, v)?;
+    v.split(',')
+        .map(|item| item.trim().parse().map_err(|_| format!("bad value {:?} for {}", item, flag)))
+        .collect()
+}
@@ -0,0 +1,267 @@
+use std::io::{self, Write};
+
+use crate::{analysis::{bounds, fairness}, engine::Simulation, metrics, process::Process, report::csv, scheduler::Policy};
+
+// Runs one workload through several policies and lines the results up, which is
+// what we otherwise end up doing by hand with a spreadsheet.
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub policy: Policy,
+    pub summary: metrics::Summary,
+    pub max_wait: i32,
+    pub jain_index: f64,
+    // when the last process finished.
+    pub makespan: i32,
+}
+
+pub fn compare(processes: &[Process], policies: &[Policy]) -> Vec<Comparison> {
+    policies
+        .iter()
+        .map(|&policy| {
+            let log = Simulation::with_policy(processes.to_vec(), policy).run();
+            Comparison {
+                policy,
+                summary: metrics::summary(&log.content),
+                max_wait: metrics::process_metrics(&log.content).iter().map(|m| m.wait).max().unwrap_or(0),
+                jain_index: fairness::fairness(&log.content, log.starvation_threshold).jain_index,
+                makespan: log.content.len() as i32,
+            }
+        })
+        .collect()
+}
+
+/// Every policy we have, with round robin once per quantum.
+pub fn all_policies(quanta: &[i32]) -> Vec<Policy> {
+    [Policy::Fcfs, Policy::Priority]
+        .into_iter()
+        .chain(quanta.iter().map(|&quantum| Policy::RoundRobin { quantum }))
+        .collect()
+}
+
+pub fn table(results: &[Comparison]) -> String {
+    let mut out = format!(
+        "{:<12} {:>9} {:>14} {:>10} {:>8} {:>8} {:>8} {:>8} {:>6}\n",
+        "POLICY", "AVG WAIT", "AVG TURNAROUND", "THROUGHPUT", "CPU", "IO", "MAX WAIT", "FAIRNESS", "TIME"
+    );
+    for result in results {
+        let summary = &result.summary;
+        out.push_str(&format!(
+            "{:<12} {:>9.2} {:>14.2} {:>10.3} {:>8.2} {:>8.2} {:>8} {:>8.3} {:>6}\n",
+            result.policy.to_string(),
+            summary.avg_wait,
+            summary.avg_turnaround,
+            summary.throughput,
+            summary.cpu_usage,
+            summary.io_usage,
+            result.max_wait,
+            result.jain_index,
+            result.makespan,
+        ));
+    }
+    out
+}
+
+/// The workload's lower bounds and how far above them each policy ended up.
+pub fn bounds_table(bounds: &bounds::Bounds, results: &[Comparison]) -> String {
+    let mut out = format!(
+        "LOWER BOUNDS: avg wait {:.2}, avg turnaround {:.2}, makespan {}\n",
+        bounds.avg_wait, bounds.avg_turnaround, bounds.makespan
+    );
+    for (name, assumption) in bounds::ASSUMPTIONS {
+        out.push_str(&format!("  {}: {}\n", name, assumption));
+    }
+    out.push_str(&format!("\n{:<12} {:>9} {:>14} {:>9}\n", "GAP", "AVG WAIT", "AVG TURNAROUND", "MAKESPAN"));
+    // the avg wait bound doesn't hold with IO, so a run can end up under it.
+    let mut below = false;
+    let mut percent = |value: f64, bound: f64| {
+        let gap = bounds::gap(value, bound);
+        below |= gap < 0.;
+        format!("{:+.1}%{}", gap * 100., if gap < 0. { "*" } else { "" })
+    };
+    for result in results {
+        out.push_str(&format!(
+            "{:<12} {:>9} {:>14} {:>9}\n",
+            result.policy.to_string(),
+            percent(result.summary.avg_wait, bounds.avg_wait),
+            percent(result.summary.avg_turnaround, bounds.avg_turnaround),
+            percent(result.makespan as f64, bounds.makespan as f64),
+        ));
+    }
+    if below {
+        out.push_str("* below reference, so that number isn't a bound for this workload\n");
+    }
+    out
+}
+
+/// A parameter and the values to run it with, e.g. `quantum` over `1..=20`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sweep {
+    pub param: String,
+    pub values: Vec<i32>,
+}
+
+impl std::str::FromStr for Sweep {
+    type Err = String;
+
+    // `<param>=<from>..<to>[:<step>]` with `to` included, or `<param>=<v>,<v>,...`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (param, range) = s
+            .split_once('=')
+            .ok_or_else(|| format!("--sweep expects <param>=<range>, got {:?}", s))?;
+        let bad = |v: &str| format!("bad sweep value {:?}", v);
+        let values = match range.split_once("..") {
+            Some((from, rest)) => {
+                let (to, step) = rest.split_once(':').unwrap_or((rest, "1"));
+                let from: i32 = from.parse().map_err(|_| bad(from))?;
+                let to: i32 = to.parse().map_err(|_| bad(to))?;
+                let step: usize = match step.parse() {
+                    Ok(step) if step > 0 => step,
+                    _ => return Err(bad(step)),
+                };
+                // nothing to run, which would only print the header.
+                if from > to {
+                    return Err(format!("empty sweep range {:?}, it goes from low to high", range));
+                }
+                (from..=to).step_by(step).collect()
+            }
+            None => range
+                .split(',')
+                .map(|v| v.trim().parse().map_err(|_| bad(v)))
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        // validates the parameter name up front rather than halfway through the runs.
+        for &value in &values {
+            Policy::with_parameter(param, value)?;
+        }
+        Ok(Self { param: param.to_string(), values })
+    }
+}
+
+pub fn sweep(processes: &[Process], sweep: &Sweep) -> Vec<Comparison> {
+    let policies = sweep
+        .values
+        .iter()
+        .map(|&value| Policy::with_parameter(&sweep.param, value).unwrap())
+        .collect::<Vec<_>>();
+    compare(processes, &policies)
+}
+
+/// One row per value, ready to be plotted against the parameter column.
+pub fn write_sweep_csv(sweep: &Sweep, results: &[Comparison], out: &mut dyn Write) -> io::Result<()> {
+    let header = [
+        sweep.param.as_str(), "policy", "avg_wait", "avg_turnaround", "throughput",
+        "cpu_usage", "io_usage", "max_wait", "jain_index", "time",
+    ];
+    csv::write_row(out, &header.map(String::from))?;
+    for (value, result) in sweep.values.iter().zip(results) {
+        let summary = &result.summary;
+        csv::write_row(
+            out,
+            &[
+                value.to_string(),
+                result.policy.to_string(),
+                summary.avg_wait.to_string(),
+                summary.avg_turnaround.to_string(),
+                summary.throughput.to_string(),
+                summary.cpu_usage.to_string(),
+                summary.io_usage.to_string(),
+                result.max_wait.to_string(),
+                result.jain_index.to_string(),
+                result.makespan.to_string(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::{Burst, BurstKind};
+
+    #[test]
+    fn test_compare() {
+        // the convoy again: FCFS runs the long one first, round robin gets the short ones out.
+        let processes = (0..3)
+            .zip([24, 3, 3])
+            .map(|(pid, burst)| Process::new(format!("P{}", pid + 1), pid, 0, vec![Burst(BurstKind::Cpu, burst)], 0))
+            .collect::<Vec<_>>();
+        let policies = all_policies(&[4]);
+        assert_eq!(policies, vec![Policy::Fcfs, Policy::Priority, Policy::RoundRobin { quantum: 4 }]);
+        let results = compare(&processes, &policies);
+        let waits = results.iter().map(|result| (result.summary.avg_wait, result.max_wait, result.makespan)).collect::<Vec<_>>();
+        assert_eq!(waits, vec![(17., 27, 30), (17., 27, 30), (17. / 3., 7, 30)]);
+
+        let table = table(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("POLICY"));
+        assert!(lines[3].starts_with("rr:4"));
+        assert!(lines[3].contains("     5.67 "));
+        // the makespan, 30 ticks, not the last tick's time.
+        assert!(lines[3].ends_with(" 30"));
+    }
+
+    #[test]
+    fn test_parse_sweep() {
+        let values = |s: &str| s.parse::<Sweep>().map(|sweep| sweep.values);
+        assert_eq!(values("quantum=1..4"), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(values("quantum=1..10:4"), Ok(vec![1, 5, 9]));
+        assert_eq!(values("quantum=3..3"), Ok(vec![3]));
+        assert_eq!(values("quantum=8, 2,5"), Ok(vec![8, 2, 5]));
+
+        assert_eq!(values("quantum=10..1"), Err("empty sweep range \"10..1\", it goes from low to high".to_string()));
+        assert_eq!(values("quantum=1..4:0"), Err("bad sweep value \"0\"".to_string()));
+        assert_eq!(values("quantum=1..4:-1"), Err("bad sweep value \"-1\"".to_string()));
+        assert_eq!(values("quantum=1,,2"), Err("bad sweep value \"\"".to_string()));
+        assert_eq!(values("quantum=0..2"), Err("bad quantum 0".to_string()));
+        assert!(values("speed=1..2").unwrap_err().starts_with("unknown parameter \"speed\""));
+        assert!(values("quantum").is_err());
+    }
+
+    #[test]
+    fn test_sweep() {
+        let processes = (0..3)
+            .zip([24, 3, 3])
+            .map(|(pid, burst)| Process::new(format!("P{}", pid + 1), pid, 0, vec![Burst(BurstKind::Cpu, burst)], 0))
+            .collect::<Vec<_>>();
+        let sweep_of = "quantum=1,24".parse::<Sweep>().unwrap();
+        let results = sweep(&processes, &sweep_of);
+        assert_eq!(
+            results.iter().map(|result| result.policy).collect::<Vec<_>>(),
+            vec![Policy::RoundRobin { quantum: 1 }, Policy::RoundRobin { quantum: 24 }]
+        );
+        let mut out = vec![];
+        write_sweep_csv(&sweep_of, &results, &mut out).unwrap();
+        let lines = String::from_utf8(out).unwrap().lines().map(String::from).collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "quantum,policy,avg_wait,avg_turnaround,throughput,cpu_usage,io_usage,max_wait,jain_index,time");
+        // a quantum as long as the longest burst is FCFS.
+        assert!(lines[2].starts_with("24,rr:24,17,27,"));
+        assert!(lines[2].ends_with(",30"));
+        assert!(lines[1].starts_with("1,rr:1,"));
+    }
+
+    #[test]
+    fn test_bounds_table_with_io() {
+        // A's IO lets B go without waiting for all of A, so every policy beats the avg wait reference.
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 1), Burst(BurstKind::Io, 10), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 5)], 0),
+        ];
+        let results = compare(&processes, &all_policies(&[2]));
+        let table = bounds_table(&bounds::bounds(&processes), &results);
+        let gaps = table.lines().skip_while(|line| !line.starts_with("GAP")).collect::<Vec<_>>();
+        assert_eq!(gaps.len(), 5);
+        assert!(gaps[1].starts_with("fcfs"));
+        assert!(gaps[1].contains("-50.0%*"));
+        assert!(!table.contains("+-"));
+        assert!(gaps[4].starts_with("* below reference"));
+
+        // nothing below it without IO, so no footnote either.
+        let processes = vec![Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2)], 0)];
+        let table = bounds_table(&bounds::bounds(&processes), &compare(&processes, &[Policy::Fcfs]));
+        assert!(table.contains("+0.0%") && !table.contains('*'));
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    log::{Log, TickEntry},
+    process::{BurstKind, Process},
+    scheduler::{fcfs::FCFS, Policy, Scheduler, SchedulerResult},
+    system_state::SystemState,
+};
+
+/// Drives a CPU and an IO scheduler one tick at a time, recording every tick in a `Log`.
+pub struct Simulation {
+    // sorted by arrival time, since we only want to add them to the scheduler once they're in.
+    processes: VecDeque<Process>,
+    cpu_sched: Box<dyn Scheduler>,
+    io_sched: Box<dyn Scheduler>,
+    finished_process_queue: Vec<Process>,
+    state: SystemState,
+    next_pid: i32,
+    // every change made since it started, in the order they were made.
+    edits: Vec<Edit>,
+    pub log: Log,
+}
+
+/// A change made to a run partway through, and the tick it was made before.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Edit {
+    pub tick: i32,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Change {
+    Inject(Process),
+    Kill(i32),
+    SetPriority { pid: i32, priority: i32 },
+    SetPolicy(Policy),
+}
+
+/// Everything a `Simulation` needs to carry on from a tick, taken before it's simulated.
+pub struct Snapshot {
+    processes: VecDeque<Process>,
+    cpu_sched: Box<dyn Scheduler>,
+    io_sched: Box<dyn Scheduler>,
+    finished_process_queue: Vec<Process>,
+    state: SystemState,
+    next_pid: i32,
+    // how long the log and the edits were, anything after this is thrown away on restore.
+    ticks: usize,
+    edits: usize,
+}
+
+impl Simulation {
+    pub fn new(mut processes: Vec<Process>, cpu_sched: Box<dyn Scheduler>, io_sched: Box<dyn Scheduler>) -> Self {
+        processes.sort_by_key(|proc| proc.arrival);
+        let next_pid = processes.iter().map(|proc| proc.pid + 1).max().unwrap_or(0);
+        Self {
+            processes: processes.into(),
+            cpu_sched,
+            io_sched,
+            finished_process_queue: vec![],
+            state: SystemState::new(),
+            next_pid,
+            edits: vec![],
+            log: Log::new(),
+        }
+    }
+
+    /// IO is always FCFS, `policy` only picks the CPU scheduler.
+    pub fn with_policy(processes: Vec<Process>, policy: Policy) -> Self {
+        Self::new(
+            processes,
+            policy.cpu_scheduler(),
+            Box::new(FCFS::new(vec![], BurstKind::Io)),
+        )
+    }
+
+    pub fn is_done(&self) -> bool {
+        !self.log.content.is_empty()
+            && self.cpu_sched.get_queue().is_empty()
+            && self.io_sched.get_queue().is_empty()
+            && self.processes.is_empty()
+    }
+
+    /// The tick `step` simulates next.
+    pub fn time(&self) -> i32 {
+        self.state.time
+    }
+
+    /// A pid nothing in the run has used yet.
+    pub fn next_pid(&self) -> i32 {
+        self.next_pid
+    }
+
+    /// The changes made so far, each as of the tick it was made before.
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Makes `change` as if it had been made through the method for it.
+    pub fn apply(&mut self, change: Change) {
+        match change {
+            Change::Inject(proc) => self.inject(proc),
+            Change::Kill(pid) => {
+                self.kill(pid);
+            }
+            Change::SetPriority { pid, priority } => {
+                self.set_priority(pid, priority);
+            }
+            Change::SetPolicy(policy) => self.set_policy(policy),
+        }
+    }
+
+    fn record(&mut self, change: Change) {
+        self.edits.push(Edit { tick: self.state.time, change });
+    }
+
+    /// Adds a process partway through the run. It can't arrive in the past, so anything
+    /// earlier than now arrives now.
+    pub fn inject(&mut self, mut proc: Process) {
+        proc.arrival = proc.arrival.max(self.state.time);
+        self.next_pid = self.next_pid.max(proc.pid + 1);
+        let index = self.processes.partition_point(|other| other.arrival <= proc.arrival);
+        self.record(Change::Inject(proc.clone()));
+        self.processes.insert(index, proc);
+    }
+
+    /// Takes a process out of the run wherever it is, it just stops existing.
+    pub fn kill(&mut self, pid: i32) -> Option<Process> {
+        let killed = match self.processes.iter().position(|proc| proc.pid == pid) {
+            Some(index) => self.processes.remove(index),
+            None => self.cpu_sched.remove(pid).or_else(|| self.io_sched.remove(pid)),
+        };
+        if killed.is_some() {
+            self.record(Change::Kill(pid));
+        }
+        killed
+    }
+
+    pub fn set_priority(&mut self, pid: i32, priority: i32) -> bool {
+        let found = match self.processes.iter_mut().find(|proc| proc.pid == pid) {
+            Some(proc) => {
+                proc.priority = priority;
+                true
+            }
+            None => self.cpu_sched.set_priority(pid, priority) || self.io_sched.set_priority(pid, priority),
+        };
+        if found {
+            self.record(Change::SetPriority { pid, priority });
+        }
+        found
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            processes: self.processes.clone(),
+            cpu_sched: self.cpu_sched.snapshot(),
+            io_sched: self.io_sched.snapshot(),
+            finished_process_queue: self.finished_process_queue.clone(),
+            state: self.state,
+            next_pid: self.next_pid,
+            ticks: self.log.content.len(),
+            edits: self.edits.len(),
+        }
+    }
+
+    /// Puts the run back to where it was at `snapshot`, forgetting every tick since. The
+    /// snapshot is left as it was so it can be gone back to again.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.processes = snapshot.processes.clone();
+        self.cpu_sched = snapshot.cpu_sched.snapshot();
+        self.io_sched = snapshot.io_sched.snapshot();
+        self.finished_process_queue = snapshot.finished_process_queue.clone();
+        self.state = snapshot.state;
+        self.next_pid = snapshot.next_pid;
+        self.log.content.truncate(snapshot.ticks);
+        self.edits.truncate(snapshot.edits);
+    }
+
+    /// Swaps the CPU scheduler from here on, handing it everything the old one was holding
+    /// in the order the old one would have run them.
+    pub fn set_policy(&mut self, policy: Policy) {
+        let mut cpu_sched = policy.cpu_scheduler();
+        for proc in self.cpu_sched.drain() {
+            cpu_sched.enqueue(proc);
+        }
+        self.cpu_sched = cpu_sched;
+        self.record(Change::SetPolicy(policy));
+    }
+
+    pub fn step(&mut self) -> &TickEntry {
+        while let Some(proc) = self.processes.front() {
+            if proc.arrival > self.state.time {
+                break;
+            }
+            self.cpu_sched.enqueue(self.processes.pop_front().unwrap());
+        }
+
+        let mut cpu_queue = vec![];
+        let mut io_queue = vec![];
+
+        let cpu_sched_result = run_sched(
+            self.cpu_sched.as_mut(),
+            &self.state,
+            &mut self.finished_process_queue,
+            &mut cpu_queue,
+            &mut io_queue,
+        );
+        let io_sched_result = run_sched(
+            self.io_sched.as_mut(),
+            &self.state,
+            &mut self.finished_process_queue,
+            &mut cpu_queue,
+            &mut io_queue,
+        );
+
+        for i in cpu_queue {
+            self.cpu_sched.enqueue(i);
+        }
+        for i in io_queue {
+            self.io_sched.enqueue(i);
+        }
+
+        self.log.push(TickEntry {
+            cpu_process: cpu_sched_result,
+            io_process: io_sched_result,
+            cpu_queue: self.cpu_sched.get_queue().into_iter().cloned().collect(),
+            io_queue: self.io_sched.get_queue().into_iter().cloned().collect(),
+            yet_to_arrive: self.processes.iter().cloned().collect(),
+            finished_processes: self.finished_process_queue.clone(),
+        });
+
+        self.state.time += 1;
+        self.log.content.last().unwrap()
+    }
+
+    pub fn run(mut self) -> Log {
+        while !self.is_done() {
+            self.step();
+        }
+        self.log
+    }
+}
+
+fn run_sched(
+    scheduler: &mut dyn Scheduler,
+    system_state: &SystemState,
+    finished_process_queue: &mut Vec<Process>,
+    cpu_queue: &mut Vec<Process>,
+    io_queue: &mut Vec<Process>,
+) -> SchedulerResult {
+    let cpu_sched_result = scheduler.tick(system_state);
+    match cpu_sched_result.clone() {
+        SchedulerResult::Finished(p) if p.burst.is_empty() => {
+            finished_process_queue.push(p.clone());
+        }
+        SchedulerResult::Finished(p) => match p.burst[0].0 {
+            BurstKind::Cpu => cpu_queue.push(p),
+            BurstKind::Io => io_queue.push(p),
+        },
+        SchedulerResult::Processing(_)
+        | SchedulerResult::Idle
+        | SchedulerResult::NoBurstLeft => {}
+        SchedulerResult::WrongKind => panic!("schedule for IO instead you idiot."),
+    };
+    cpu_sched_result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metrics, process::Burst};
+
+    #[test]
+    fn test_live_changes() {
+        let processes = vec![
+            Process::new("A".to_string(), 0, 3, vec![Burst(BurstKind::Cpu, 4)], 0),
+            Process::new("B".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 2)], 0),
+        ];
+        let mut sim = Simulation::with_policy(processes, Policy::Priority);
+        sim.step();
+        // C asks to arrive at 0 but it's already 1, and it outranks everything.
+        let pid = sim.next_pid();
+        sim.inject(Process::new("C".to_string(), pid, 0, vec![Burst(BurstKind::Cpu, 1)], 0));
+        assert_eq!(sim.next_pid(), 3);
+        // A goes from the back to the front of the line, once C is done.
+        assert!(sim.set_priority(0, 1));
+        assert!(!sim.set_priority(7, 1));
+        let log = sim.run();
+        let completions = |pid| metrics::completion_time(pid, &log.content);
+        assert_eq!(metrics::arrival_time(2, &log.content), Some(1));
+        assert_eq!((completions(0), completions(1), completions(2)), (Some(6), Some(7), Some(2)));
+
+        let mut sim = Simulation::with_policy(
+            vec![Process::new("A".to_string(), 0, 0, vec![Burst(BurstKind::Cpu, 5)], 0)],
+            Policy::Fcfs,
+        );
+        sim.step();
+        assert_eq!(sim.kill(0).map(|proc| proc.burst[0].1), Some(4));
+        assert!(sim.is_done());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let processes = vec![
+            Process::new("A".to_string(), 0, 2, vec![Burst(BurstKind::Cpu, 4), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 3)], 1),
+            Process::new("C".to_string(), 2, 3, vec![Burst(BurstKind::Cpu, 1)], 5),
+        ];
+        let cpu = |log: &Log| log.content.iter().map(|entry| entry.cpu_process.clone()).collect::<Vec<_>>();
+        let whole = Simulation::with_policy(processes.clone(), Policy::RoundRobin { quantum: 2 }).run();
+
+        // partway through A's turn, so round robin has to remember how much of it is left.
+        let mut sim = Simulation::with_policy(processes, Policy::RoundRobin { quantum: 2 });
+        for _ in 0..3 {
+            sim.step();
+        }
+        let snapshot = sim.snapshot();
+        while !sim.is_done() {
+            sim.step();
+        }
+        sim.restore(&snapshot);
+        assert_eq!((sim.time(), sim.log.content.len()), (3, 3));
+        while !sim.is_done() {
+            sim.step();
+        }
+        assert_eq!(cpu(&sim.log), cpu(&whole));
+
+        // from the same point under priority B, which outranks A, gets to finish its burst uninterrupted.
+        sim.restore(&snapshot);
+        sim.set_policy(Policy::Priority);
+        assert_eq!(sim.edits(), [Edit { tick: 3, change: Change::SetPolicy(Policy::Priority) }]);
+        let log = sim.run();
+        let completions = |log: &Log| (0..3).map(|pid| metrics::completion_time(pid, &log.content)).collect::<Vec<_>>();
+        assert_eq!(completions(&whole), vec![Some(10), Some(8), Some(6)]);
+        assert_eq!(completions(&log), vec![Some(10), Some(5), Some(8)]);
+    }
+}
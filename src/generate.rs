@@ -0,0 +1,91 @@
+use crate::process::{Burst, BurstKind, Process};
+
+// Random workloads for batch runs. Seeded so a batch can be rerun exactly, and
+// hand rolled since splitmix is a dozen lines and not worth a dependency.
+
+/// SplitMix64, good enough for making up workloads, not for anything else.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..=high`.
+    pub fn range(&mut self, low: i32, high: i32) -> i32 {
+        let span = (high - low) as u64 + 1;
+        low + (self.next_u64() % span) as i32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub processes: usize,
+    // arrivals are spread over 0..=max_arrival.
+    pub max_arrival: i32,
+    pub max_priority: i32,
+    // CPU bursts per process, there's an IO burst between each pair.
+    pub max_cpu_bursts: i32,
+    pub max_burst: i32,
+}
+
+impl Config {
+    pub fn new(processes: usize) -> Self {
+        Self {
+            processes,
+            max_arrival: processes as i32 * 3,
+            max_priority: 5,
+            max_cpu_bursts: 3,
+            max_burst: 10,
+        }
+    }
+}
+
+pub fn workload(config: &Config, rng: &mut Rng) -> Vec<Process> {
+    (0..config.processes)
+        .map(|pid| {
+            let mut bursts = vec![];
+            for i in 0..rng.range(1, config.max_cpu_bursts) {
+                if i > 0 {
+                    bursts.push(Burst(BurstKind::Io, rng.range(1, config.max_burst)));
+                }
+                bursts.push(Burst(BurstKind::Cpu, rng.range(1, config.max_burst)));
+            }
+            Process::new(
+                format!("P{}", pid),
+                pid as i32,
+                rng.range(1, config.max_priority),
+                bursts,
+                rng.range(0, config.max_arrival),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workload() {
+        let config = Config::new(20);
+        let first = workload(&config, &mut Rng::new(7));
+        assert_eq!(first, workload(&config, &mut Rng::new(7)));
+        assert_ne!(first, workload(&config, &mut Rng::new(8)));
+        for proc in &first {
+            assert!((0..=config.max_arrival).contains(&proc.arrival));
+            // CPU first and last, alternating in between.
+            assert_eq!(proc.burst.len() % 2, 1);
+            assert!(proc.burst.iter().enumerate().all(|(i, burst)| (burst.0 == BurstKind::Cpu) == (i % 2 == 0)));
+        }
+    }
+}
@@ -1,194 +1,225 @@
-use std::collections::VecDeque;
-
+use engine::Simulation;
 use log::Log;
 use process::{Burst, BurstKind, Process};
-use scheduler::{fcfs::FCFS, Scheduler, SchedulerResult};
-use system_state::SystemState;
-
+use saved::SavedRun;
+use scheduler::Policy;
+
+mod analysis;
+mod batch;
+mod cli;
+mod compare;
+mod engine;
+mod generate;
 mod log;
+mod metrics;
 mod process;
+mod report;
+mod saved;
 mod scheduler;
+mod stats;
 mod system_state;
+mod trace;
+mod ui;
 
 fn main() {
     let mut args = std::env::args();
     let _ = args.next().unwrap();
-    let file = match args.next() {
-        Some(v) => v,
-        None => panic!("Please pass in a file name"),
+    let options = match cli::Options::parse(args) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
     };
-    let content = std::fs::read_to_string(&file).unwrap();
-    let mut processes: Vec<Process> = content
-        .lines()
-        .enumerate()
-        .map(|(pid, line)| {
-            let mut process_info = line.split(" ");
-            let name = process_info.next().unwrap();
-            let arrival_time = process_info.next().unwrap().parse().unwrap();
-            let priority = process_info.next().unwrap().parse().unwrap();
-            let mut next = BurstKind::Cpu;
-            let mut bursts = vec![];
-            while let Some(v) = process_info.next() {
-                bursts.push(Burst(next, v.parse().unwrap()));
-                next = match next {
-                    BurstKind::Cpu => BurstKind::Io,
-                    BurstKind::Io => BurstKind::Cpu,
-                };
+    if let Some(runs) = options.batch {
+        let policies = match options.scheduler {
+            Some(policy) => std::iter::once(policy).chain(options.versus).collect(),
+            None => compare::all_policies(&options.quanta),
+        };
+        let results = batch::run(&policies, runs, options.seed, &generate::Config::new(options.procs));
+        print!("{}", batch::report(&policies, &results));
+        return;
+    }
+
+    let config = match &options.config {
+        Some(path) => match ui::config::Config::load(path) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                std::process::exit(1);
             }
-            Process::new(
-                name.to_owned(),
-                pid.try_into().unwrap(),
-                priority,
-                bursts,
-                arrival_time,
-            )
-        })
-        .collect();
-    // sort them to be sorted by arrival time, since we only want to add them to the scheduler once they're in.
-    processes.sort_unstable_by(|proc, other_proc| proc.arrival.cmp(&other_proc.arrival));
+        },
+        None => ui::config::Config::default(),
+    };
 
-    println!("Press 1 for FCFS\nPress 2 for Priority\nPress 3 for Round Robin");
-    let mut buff = String::new();
+    if let Some(path) = &options.open {
+        match SavedRun::load(path) {
+            Ok(saved) => finish(&saved, &options, &config),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
 
-    std::io::stdin().read_line(&mut buff).unwrap();
-    let choice: i32 = buff.trim().parse().unwrap();
+    let file = options.file.as_deref().unwrap();
+    let content = std::fs::read_to_string(file).unwrap();
+    let processes = match options.format {
+        cli::InputFormat::Workload => Ok(parse_workload(&content)),
+        cli::InputFormat::TraceCsv => trace::from_csv(&content, options.tick),
+        cli::InputFormat::ProcStat => trace::from_proc_stat(&content, options.tick),
+    };
+    let processes = match processes {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("{}: {}", file, e);
+            std::process::exit(1);
+        }
+    };
 
-    match choice {
-        1 => start_sim(
-            processes.into_iter().collect(),
-            FCFS::new(vec![], BurstKind::Cpu),
-            FCFS::new(vec![], BurstKind::Io),
-        ),
-        2 => start_sim(
-            processes.into_iter().collect(),
-            scheduler::priority::Priority::new(vec![], BurstKind::Cpu),
-            FCFS::new(vec![], BurstKind::Io),
-        ),
-        3 => {
-            println!("What quantum time would you like? ");
+    if options.compare {
+        let results = compare::compare(&processes, &compare::all_policies(&options.quanta));
+        print!("{}", compare::table(&results));
+        println!();
+        print!("{}", compare::bounds_table(&analysis::bounds::bounds(&processes), &results));
+        return;
+    }
 
-            buff.clear();
-            std::io::stdin().read_line(&mut buff).unwrap();
-            let quantum_time: i32 = buff.trim().parse().unwrap();
-            start_sim(
-                processes.into_iter().collect(),
-                scheduler::round_robin::RoundRobin::new(vec![], BurstKind::Cpu, quantum_time),
-                FCFS::new(vec![], BurstKind::Io),
-            )
-        },
-        _ => {
-            panic!("Unsupported choice.")
-        }
+    if let Some(sweep) = &options.sweep {
+        let results = compare::sweep(&processes, sweep);
+        compare::write_sweep_csv(sweep, &results, &mut std::io::stdout().lock()).unwrap();
+        return;
     }
 
+    if let (Some(policy), Some(versus)) = (options.scheduler, options.versus) {
+        let sides = [policy, versus].map(|policy| {
+            let mut log = Simulation::with_policy(processes.clone(), policy).run();
+            log.starvation_threshold = options.starvation_threshold;
+            log.window = options.window;
+            ui::side_by_side::Side { policy, log }
+        });
+        ui::side_by_side::run(sides, &config);
+        return;
+    }
 
+    let policy = match options.scheduler {
+        Some(policy) => policy,
+        None => ask_policy(),
+    };
     // this is somewhat bad design, both CPU and IO schedulers share a type (willfully, it lets me reuse code)
     // but instead of storing the BurstKind as a field, it probably would of been better to make a type like
     // BurstKindCpu<FCFS> and BurstKindIo<FCFS>. Oh well. That would of had it's own complexities.
     // ...I can just do a runtime check to validate them but that's not hip and cool.
+    let mut sim = Simulation::with_policy(processes.clone(), policy);
+    sim.log.starvation_threshold = options.starvation_threshold;
+    sim.log.window = options.window;
+    let (log, edits) = if options.live {
+        let sim = ui::live::run(sim, &config);
+        let edits = sim.edits().to_vec();
+        (sim.log, edits)
+    } else {
+        (sim.run(), vec![])
+    };
+    finish(&SavedRun::new(processes, policy, options.live, edits, log), &options, &config);
 }
 
-fn run_sched(
-    scheduler: &mut impl Scheduler,
-    system_state: &SystemState,
-    finished_process_queue: &mut Vec<Process>,
-    cpu_queue: &mut Vec<Process>,
-    io_queue: &mut Vec<Process>,
-) -> SchedulerResult {
-    let cpu_sched_result = scheduler.tick(system_state);
-    match cpu_sched_result.clone() {
-        scheduler::SchedulerResult::Finished(p) if p.burst.len() == 0 => {
-            finished_process_queue.push(p.clone());
+// shows, saves and exports a run, however it came to be.
+fn finish(saved: &SavedRun, options: &cli::Options, config: &ui::config::Config) {
+    let log = &saved.log;
+    if options.tui && !options.live {
+        log.draw_gui(config);
+    }
+
+    if let Some(path) = &options.save {
+        if let Err(e) = saved.save(path) {
+            eprintln!("Couldn't write {}: {}", path, e);
         }
-        scheduler::SchedulerResult::Finished(p) => match p.burst[0].0 {
-            BurstKind::Cpu => cpu_queue.push(p),
-            BurstKind::Io => io_queue.push(p),
-        },
-        scheduler::SchedulerResult::Processing(_)
-        | scheduler::SchedulerResult::Idle
-        | scheduler::SchedulerResult::NoBurstLeft => {}
-        scheduler::SchedulerResult::WrongKind => panic!("schedule for IO instead you idiot."),
-    };
-    cpu_sched_result
-}
+    }
 
-fn start_sim(
-    mut processes: VecDeque<Process>,
-    mut cpu_sched: impl Scheduler,
-    mut io_sched: impl Scheduler,
-) {
-    let mut finished_process_queue = vec![];
+    if !options.exports.is_empty() || options.save.is_some() {
+        for export in &options.exports {
+            write_export(export, log);
+        }
+        return;
+    }
 
-    let mut log = Log::new();
+    println!(
+        "If you want to write to a file, input it's name. Otherwise just press enter."
+    );
 
-    let mut state = SystemState::new();
+    let mut buff = String::new();
+    std::io::stdin().read_line(&mut buff).unwrap();
 
-    loop {
-        match processes.front() {
-            Some(proc) if proc.arrival <= state.time => {
-                cpu_sched.enqueue(processes.pop_front().unwrap());
-                continue;
-            }
-            _ => {}
-        }
+    if buff.trim().is_empty() {
+        return;
+    }
 
-        let mut cpu_queue = vec![];
-        let mut io_queue = vec![];
-
-        let cpu_sched_result = run_sched(
-            &mut cpu_sched,
-            &state,
-            &mut finished_process_queue,
-            &mut cpu_queue,
-            &mut io_queue,
-        );
-        let io_sched_result = run_sched(
-            &mut io_sched,
-            &state,
-            &mut finished_process_queue,
-            &mut cpu_queue,
-            &mut io_queue,
-        );
-
-        for i in cpu_queue {
-            cpu_sched.enqueue(i);
-        }
-        for i in io_queue {
-            io_sched.enqueue(i);
-        }
+    let file = std::fs::File::create(buff.trim());
+    log.write_file(&mut file.unwrap());
+}
 
-        log.push(log::TickEntry {
-            cpu_process: cpu_sched_result,
-            io_process: io_sched_result,
-            cpu_queue: cpu_sched.get_queue().into_iter().cloned().collect(),
-            io_queue: io_sched.get_queue().into_iter().cloned().collect(),
-            yet_to_arrive: processes.clone().into_iter().collect(),
-            finished_processes: finished_process_queue.clone(),
-        });
+fn ask_policy() -> Policy {
+    println!("Press 1 for FCFS\nPress 2 for Priority\nPress 3 for Round Robin");
+    let mut buff = String::new();
 
-        state.time += 1;
+    std::io::stdin().read_line(&mut buff).unwrap();
+    let choice: i32 = buff.trim().parse().unwrap();
 
-        if cpu_sched.get_queue().is_empty()
-            && io_sched.get_queue().is_empty()
-            && processes.is_empty()
-        {
-            log.draw_gui();
-            println!();
-            println!(
-                "If you want to write to a file, input it's name. Otherwise just press enter."
-            );
+    match choice {
+        1 => Policy::Fcfs,
+        2 => Policy::Priority,
+        3 => {
+            println!("What quantum time would you like? ");
 
-            let mut buff = String::new();
+            buff.clear();
             std::io::stdin().read_line(&mut buff).unwrap();
+            let quantum: i32 = buff.trim().parse().unwrap();
+            Policy::RoundRobin { quantum }
+        },
+        _ => {
+            panic!("Unsupported choice.")
+        }
+    }
+}
 
-            if buff.trim().is_empty() {
-                return;
+fn parse_workload(content: &str) -> Vec<Process> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(pid, line)| {
+            let mut process_info = line.split(" ");
+            let name = process_info.next().unwrap();
+            let arrival_time = process_info.next().unwrap().parse().unwrap();
+            let priority = process_info.next().unwrap().parse().unwrap();
+            let mut next = BurstKind::Cpu;
+            let mut bursts = vec![];
+            for v in process_info {
+                bursts.push(Burst(next, v.parse().unwrap()));
+                next = match next {
+                    BurstKind::Cpu => BurstKind::Io,
+                    BurstKind::Io => BurstKind::Cpu,
+                };
             }
+            Process::new(
+                name.to_owned(),
+                pid.try_into().unwrap(),
+                priority,
+                bursts,
+                arrival_time,
+            )
+        })
+        .collect()
+}
 
-            let file = std::fs::File::create(buff.trim());
-            log.write_file(&mut file.unwrap());
-
-            return;
-        }
+fn write_export(export: &cli::Export, log: &Log) {
+    let result = if export.path == "-" {
+        report::export(export.kind, log, &mut std::io::stdout().lock())
+    } else {
+        std::fs::File::create(&export.path)
+            .and_then(|mut f| report::export(export.kind, log, &mut f))
+    };
+    if let Err(e) = result {
+        eprintln!("Couldn't write {}: {}", export.path, e);
     }
 }
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::{log::TickEntry, process::Process, scheduler::SchedulerResult, stats};
+
+// Everything here works off of the log of a (possibly partial) run, so the TUI can
+// show the numbers as of any tick and the exporters can show them for the whole run.
+
+pub fn all_processes(content: &TickEntry) -> Vec<Process> {
+    content
+        .cpu_queue
+        .iter()
+        .chain(content.io_queue.iter())
+        .chain(content.finished_processes.iter())
+        .chain(content.yet_to_arrive.iter())
+        .cloned()
+        .collect()
+}
+
+fn entry_processes(entry: &TickEntry) -> impl Iterator<Item = &Process> {
+    entry
+        .cpu_queue
+        .iter()
+        .chain(entry.io_queue.iter())
+        .chain(entry.finished_processes.iter())
+        .chain(entry.yet_to_arrive.iter())
+}
+
+/// Every process in the run: the ones in the first tick, then any that only show up
+/// later, which only happens when they're added partway through a live run.
+pub fn processes(content: &[TickEntry]) -> Vec<Process> {
+    let mut processes = all_processes(&content[0]);
+    let mut seen = processes.iter().map(|proc| proc.pid).collect::<HashSet<_>>();
+    for entry in &content[1..] {
+        for proc in entry_processes(entry) {
+            if seen.insert(proc.pid) {
+                processes.push(proc.clone());
+            }
+        }
+    }
+    processes
+}
+
+// the index of the tick the process finished in.
+pub fn finished_time(pid: i32, content: &[TickEntry]) ->  Option<i32> {
+    Some(content.iter().enumerate().find(|(_time, entry)| entry.finished_processes.iter().any(|proc| proc.pid == pid))?.0 as i32)
+}
+
+// the process is done at the end of the tick it finished in, not the start.
+pub fn completion_time(pid: i32, content: &[TickEntry]) -> Option<i32> {
+    Some(finished_time(pid, content)? + 1)
+}
+
+pub fn arrival_time(pid: i32, content: &[TickEntry]) -> Option<i32> {
+    // almost always in the first tick, but a process added partway through a live run
+    // only shows up from the tick it was added in.
+    content
+        .iter()
+        .find_map(|entry| entry_processes(entry).find(|proc| proc.pid == pid))
+        .map(|proc| proc.arrival)
+}
+
+// time spent in the system without being on the CPU or IO, which includes time
+// waiting in the IO queue. Worked out from what actually ran rather than the bursts
+// in the first tick, since whatever ran in the first tick has already been decremented.
+pub fn wait_time(pid: i32, content: &[TickEntry]) -> Option<i32> {
+    Some(turn_around_time(pid, content)? - cpu_time(pid, content) - io_time(pid, content))
+}
+
+pub fn turn_around_time(pid: i32, content: &[TickEntry]) -> Option<i32> {
+    Some(completion_time(pid, content)?  -  arrival_time(pid, content)?)
+}
+
+pub fn throughput(content: &[TickEntry]) -> f64 {
+    content.last().unwrap().finished_processes.len() as f64 / content.len() as f64
+}
+
+fn is_busy(result: &SchedulerResult) -> bool {
+    matches!(result, SchedulerResult::Finished(_) | SchedulerResult::Processing(_))
+}
+
+/// The process using the device this tick, if any.
+pub fn scheduled_pid(result: &SchedulerResult) -> Option<i32> {
+    match result {
+        SchedulerResult::Finished(p) | SchedulerResult::Processing(p) => Some(p.pid),
+        _ => None,
+    }
+}
+
+pub fn first_run_time(pid: i32, content: &[TickEntry]) -> Option<i32> {
+    Some(content.iter().position(|entry| scheduled_pid(&entry.cpu_process) == Some(pid))? as i32)
+}
+
+pub fn response_time(pid: i32, content: &[TickEntry]) -> Option<i32> {
+    Some(first_run_time(pid, content)? - arrival_time(pid, content)?)
+}
+
+pub fn cpu_time(pid: i32, content: &[TickEntry]) -> i32 {
+    content.iter().filter(|entry| scheduled_pid(&entry.cpu_process) == Some(pid)).count() as i32
+}
+
+pub fn io_time(pid: i32, content: &[TickEntry]) -> i32 {
+    content.iter().filter(|entry| scheduled_pid(&entry.io_process) == Some(pid)).count() as i32
+}
+
+// times the process was taken off the CPU with some of its burst still left.
+pub fn preemptions(pid: i32, content: &[TickEntry]) -> i32 {
+    content
+        .windows(2)
+        .filter(|ticks| match &ticks[0].cpu_process {
+            SchedulerResult::Processing(p) => p.pid == pid && scheduled_pid(&ticks[1].cpu_process) != Some(pid),
+            _ => false,
+        })
+        .count() as i32
+}
+
+pub fn cpu_usage(content: &[TickEntry]) -> f64 {
+    content.iter().filter(|entry| is_busy(&entry.cpu_process)).count() as f64 / content.len() as f64
+}
+
+pub fn io_usage(content: &[TickEntry]) -> f64 {
+    content.iter().filter(|entry| is_busy(&entry.io_process)).count() as f64 / content.len() as f64
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessMetrics {
+    pub pid: i32,
+    pub name: String,
+    pub priority: i32,
+    pub arrival: i32,
+    pub first_run: i32,
+    pub completion: i32,
+    pub turnaround: i32,
+    pub wait: i32,
+    pub response: i32,
+    pub cpu_time: i32,
+    pub io_time: i32,
+    pub preemptions: i32,
+}
+
+/// Metrics for every process that has finished by the end of `content`.
+pub fn process_metrics(content: &[TickEntry]) -> Vec<ProcessMetrics> {
+    content
+        .last()
+        .unwrap()
+        .finished_processes
+        .iter()
+        // a process can only be missing one of these if it never ran, which a finished
+        // process can't have done. Skip it if it does.
+        .filter_map(|proc| Some(ProcessMetrics {
+            pid: proc.pid,
+            name: proc.name.clone(),
+            priority: proc.priority,
+            arrival: arrival_time(proc.pid, content)?,
+            first_run: first_run_time(proc.pid, content)?,
+            completion: completion_time(proc.pid, content)?,
+            turnaround: turn_around_time(proc.pid, content)?,
+            wait: wait_time(proc.pid, content)?,
+            response: response_time(proc.pid, content)?,
+            cpu_time: cpu_time(proc.pid, content),
+            io_time: io_time(proc.pid, content),
+            preemptions: preemptions(proc.pid, content),
+        }))
+        .collect()
+}
+
+/// The numbers shown in the "SYSTEM STATE" pane.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub time: usize,
+    pub cpu_usage: f64,
+    pub io_usage: f64,
+    pub avg_wait: f64,
+    pub avg_turnaround: f64,
+    pub avg_response: f64,
+    pub throughput: f64,
+}
+
+pub fn summary(content: &[TickEntry]) -> Summary {
+    let stats = stats::statistics(&process_metrics(content));
+    // nothing finished yet means nothing to average, call that 0 like the TUI always has.
+    let mean = |dist: Option<stats::Distribution>| dist.map(|dist| dist.mean).unwrap_or(0.);
+    Summary {
+        time: content.len() - 1,
+        cpu_usage: cpu_usage(content),
+        io_usage: io_usage(content),
+        avg_wait: mean(stats.wait),
+        avg_turnaround: mean(stats.turnaround),
+        avg_response: mean(stats.response),
+        throughput: throughput(content),
+    }
+}
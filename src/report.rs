@@ -0,0 +1,169 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::{analysis::{anomaly, timeseries}, log::{Log, TickEntry}, metrics, scheduler::SchedulerResult};
+
+pub mod chrome_trace;
+pub mod csv;
+pub mod gantt;
+pub mod json;
+pub mod svg;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    // the same lines `Log::write_file` writes.
+    Text,
+    Json,
+    JsonLines,
+    // per-process metrics, one row each.
+    Csv,
+    // ASCII Gantt chart.
+    Gantt,
+    Svg,
+    // the SVG chart plus the metrics tables.
+    Html,
+    // Chrome trace-event JSON, for chrome://tracing and Perfetto.
+    ChromeTrace,
+    // per-tick utilization and queue lengths as CSV.
+    TimeSeries,
+    // convoys, priority inversions and the like, as a readable report.
+    Anomalies,
+}
+
+impl FromStr for ExportKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::JsonLines),
+            "csv" => Ok(Self::Csv),
+            "gantt" => Ok(Self::Gantt),
+            "svg" => Ok(Self::Svg),
+            "html" => Ok(Self::Html),
+            "trace" => Ok(Self::ChromeTrace),
+            "timeseries" => Ok(Self::TimeSeries),
+            "anomalies" => Ok(Self::Anomalies),
+            other => Err(format!("unknown export kind {:?}", other)),
+        }
+    }
+}
+
+pub fn export(kind: ExportKind, log: &Log, out: &mut dyn Write) -> io::Result<()> {
+    let content = &log.content;
+    match kind {
+        ExportKind::Text => writeln!(out, "{}", Log::get_log_content(content, log.starvation_threshold).join("\n")),
+        ExportKind::Json => json::write_json(content, log.starvation_threshold, out),
+        ExportKind::JsonLines => json::write_json_lines(content, log.starvation_threshold, out),
+        ExportKind::Csv => csv::write_metrics(content, out),
+        ExportKind::Gantt => gantt::write_gantt(content, out),
+        ExportKind::Svg => svg::write_svg(content, out),
+        ExportKind::Html => svg::write_html(content, out),
+        ExportKind::ChromeTrace => chrome_trace::write_chrome_trace(content, out),
+        ExportKind::TimeSeries => timeseries::write_csv(content, log.window, out),
+        ExportKind::Anomalies => anomaly::write_report(content, out),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    Cpu,
+    Io,
+}
+
+/// A stretch of ticks where one process had a device to itself. `end` is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub lane: Lane,
+    pub pid: i32,
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+    // whether the burst was done by the end of the segment, as opposed to being preempted.
+    pub burst_finished: bool,
+    // what the process still had to do once the segment ended.
+    pub remaining: Vec<i32>,
+}
+
+pub fn segments(content: &[TickEntry]) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = vec![];
+    for lane in [Lane::Cpu, Lane::Io] {
+        // the index of the segment still being extended, if any.
+        let mut open: Option<usize> = None;
+        for (time, entry) in content.iter().enumerate() {
+            let result = match lane {
+                Lane::Cpu => &entry.cpu_process,
+                Lane::Io => &entry.io_process,
+            };
+            let (proc, finished) = match result {
+                SchedulerResult::Processing(p) => (p, false),
+                SchedulerResult::Finished(p) => (p, true),
+                _ => {
+                    open = None;
+                    continue;
+                }
+            };
+            let remaining = proc.burst.iter().map(|burst| burst.1).collect();
+            match open {
+                Some(i) if segments[i].pid == proc.pid => {
+                    segments[i].end = time + 1;
+                    segments[i].burst_finished = finished;
+                    segments[i].remaining = remaining;
+                }
+                _ => {
+                    segments.push(Segment {
+                        lane,
+                        pid: proc.pid,
+                        name: proc.name.clone(),
+                        start: time,
+                        end: time + 1,
+                        burst_finished: finished,
+                        remaining,
+                    });
+                    open = Some(segments.len() - 1);
+                }
+            }
+            if finished {
+                open = None;
+            }
+        }
+    }
+    segments
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerKind {
+    Arrival,
+    Preemption,
+    Completion,
+}
+
+/// Something that happened to a process at a tick boundary, e.g. a completion at
+/// `time` means the process finished during tick `time - 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    pub kind: MarkerKind,
+    pub time: usize,
+    pub pid: i32,
+    pub name: String,
+}
+
+pub fn markers(content: &[TickEntry]) -> Vec<Marker> {
+    let mut markers = vec![];
+    for proc in metrics::processes(content) {
+        if (proc.arrival as usize) < content.len() {
+            markers.push(Marker { kind: MarkerKind::Arrival, time: proc.arrival as usize, pid: proc.pid, name: proc.name.clone() });
+        }
+        if let Some(completion) = metrics::completion_time(proc.pid, content) {
+            markers.push(Marker { kind: MarkerKind::Completion, time: completion as usize, pid: proc.pid, name: proc.name });
+        }
+    }
+    for segment in segments(content) {
+        if segment.lane == Lane::Cpu && !segment.burst_finished && segment.end < content.len() {
+            markers.push(Marker { kind: MarkerKind::Preemption, time: segment.end, pid: segment.pid, name: segment.name });
+        }
+    }
+    markers.sort_by_key(|marker| marker.time);
+    markers
+}
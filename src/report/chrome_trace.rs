@@ -0,0 +1,142 @@
+use std::io::{self, Write};
+
+use serde_json::{json, Value};
+
+use crate::log::TickEntry;
+
+use super::{markers, segments, Lane, MarkerKind};
+
+// Chrome's Trace Event Format, which chrome://tracing and ui.perfetto.dev both open.
+// Timestamps are in microseconds there, we map one tick to one millisecond so the
+// default zoom levels are sensible.
+
+const TICK_US: usize = 1000;
+const PID: i32 = 1;
+const CPU_TID: i32 = 0;
+const IO_TID: i32 = 1;
+// arrivals and completions get their own track so they don't get lost between slices.
+const EVENTS_TID: i32 = 2;
+
+fn tid(lane: Lane) -> i32 {
+    match lane {
+        Lane::Cpu => CPU_TID,
+        Lane::Io => IO_TID,
+    }
+}
+
+fn thread_name(tid: i32, name: &str) -> Value {
+    json!({ "ph": "M", "name": "thread_name", "pid": PID, "tid": tid, "args": { "name": name } })
+}
+
+pub fn trace_events(content: &[TickEntry]) -> Vec<Value> {
+    let mut events = vec![
+        json!({ "ph": "M", "name": "process_name", "pid": PID, "args": { "name": "scheduler" } }),
+        thread_name(CPU_TID, "CPU0"),
+        thread_name(IO_TID, "IO0"),
+        thread_name(EVENTS_TID, "events"),
+    ];
+
+    for segment in segments(content) {
+        events.push(json!({
+            "ph": "X",
+            "name": segment.name,
+            "cat": match segment.lane { Lane::Cpu => "cpu", Lane::Io => "io" },
+            "pid": PID,
+            "tid": tid(segment.lane),
+            "ts": segment.start * TICK_US,
+            "dur": (segment.end - segment.start) * TICK_US,
+            "args": {
+                "pid": segment.pid,
+                "start_tick": segment.start,
+                "end_tick": segment.end,
+                "burst_finished": segment.burst_finished,
+                "remaining_bursts": segment.remaining,
+            },
+        }));
+    }
+
+    for marker in markers(content) {
+        let name = match marker.kind {
+            MarkerKind::Arrival => "arrival",
+            MarkerKind::Completion => "completion",
+            // preemptions already show up as the end of a slice.
+            MarkerKind::Preemption => continue,
+        };
+        events.push(json!({
+            "ph": "i",
+            "s": "t",
+            "name": format!("{} {}", name, marker.name),
+            "cat": name,
+            "pid": PID,
+            "tid": EVENTS_TID,
+            "ts": marker.time * TICK_US,
+            "args": { "pid": marker.pid, "tick": marker.time },
+        }));
+    }
+    events
+}
+
+pub fn write_chrome_trace(content: &[TickEntry], out: &mut dyn Write) -> io::Result<()> {
+    let trace = json!({
+        "traceEvents": trace_events(content),
+        "displayTimeUnit": "ms",
+    });
+    serde_json::to_writer(&mut *out, &trace)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    #[test]
+    fn test_trace_events() {
+        // A runs 0-2 then does IO while B runs, so both lanes get slices.
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 3)], 1),
+        ];
+        let log = Simulation::with_policy(processes, Policy::RoundRobin { quantum: 2 }).run();
+        let mut out = vec![];
+        write_chrome_trace(&log.content, &mut out).unwrap();
+        let trace: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(trace["displayTimeUnit"], "ms");
+        let events = trace["traceEvents"].as_array().unwrap();
+        let of = |ph: &str| events.iter().filter(|event| event["ph"] == ph).collect::<Vec<_>>();
+        assert_eq!(of("M").len(), 4);
+
+        // one slice per segment, on its device's track.
+        let slices = of("X");
+        let segments = segments(&log.content);
+        assert_eq!(slices.len(), segments.len());
+        for segment in &segments {
+            let matching = slices
+                .iter()
+                .filter(|slice| slice["args"]["pid"] == segment.pid && slice["args"]["start_tick"] == segment.start)
+                .filter(|slice| slice["tid"] == tid(segment.lane))
+                .collect::<Vec<_>>();
+            assert_eq!(matching.len(), 1);
+            assert_eq!(matching[0]["ts"], segment.start * TICK_US);
+            assert_eq!(matching[0]["dur"], (segment.end - segment.start) * TICK_US);
+            assert_eq!(matching[0]["name"], segment.name);
+        }
+        assert_eq!(
+            (&slices[0]["name"], &slices[0]["ts"], &slices[0]["dur"], &slices[0]["cat"]),
+            (&json!("A"), &json!(0), &json!(2000), &json!("cpu"))
+        );
+
+        // an arrival and a completion each, on the events track.
+        let instants = of("i");
+        let mut names = instants.iter().map(|event| event["name"].as_str().unwrap()).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["arrival A", "arrival B", "completion A", "completion B"]);
+        assert!(instants.iter().all(|event| event["tid"] == EVENTS_TID && event["s"] == "t"));
+        let arrival_b = instants.iter().find(|event| event["name"] == "arrival B").unwrap();
+        assert_eq!(arrival_b["ts"], TICK_US);
+    }
+}
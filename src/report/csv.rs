@@ -0,0 +1,107 @@
+use std::io::{self, Write};
+
+use crate::{log::TickEntry, metrics};
+
+// Just enough CSV to keep spreadsheets happy: fields with a comma, quote or newline
+// get quoted, with quotes doubled.
+pub fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn write_row(out: &mut dyn Write, fields: &[String]) -> io::Result<()> {
+    let row = fields.iter().map(|value| field(value)).collect::<Vec<_>>().join(",");
+    writeln!(out, "{}", row)
+}
+
+/// One row per finished process.
+pub fn write_metrics(content: &[TickEntry], out: &mut dyn Write) -> io::Result<()> {
+    let header = [
+        "pid", "name", "priority", "arrival", "first_run", "completion", "turnaround",
+        "wait", "response", "cpu_time", "io_time", "preemptions",
+    ];
+    write_row(out, &header.map(String::from))?;
+    for m in metrics::process_metrics(content) {
+        write_row(
+            out,
+            &[
+                m.pid.to_string(),
+                m.name,
+                m.priority.to_string(),
+                m.arrival.to_string(),
+                m.first_run.to_string(),
+                m.completion.to_string(),
+                m.turnaround.to_string(),
+                m.wait.to_string(),
+                m.response.to_string(),
+                m.cpu_time.to_string(),
+                m.io_time.to_string(),
+                m.preemptions.to_string(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    // reads back a row `write_row` wrote, the way a spreadsheet would.
+    fn parse_row(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    fields.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(String::new()),
+                c => fields.last_mut().unwrap().push(c),
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn test_field() {
+        assert_eq!(field("plain"), "plain");
+        assert_eq!(field("a,b"), "\"a,b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+
+        let name = "say \"hi\", bob";
+        let mut out = vec![];
+        write_row(&mut out, &["1".to_string(), name.to_string(), "".to_string()]).unwrap();
+        let line = String::from_utf8(out).unwrap();
+        assert_eq!(parse_row(line.trim_end()), ["1", name, ""]);
+    }
+
+    #[test]
+    fn test_write_metrics() {
+        // FCFS: A runs 0-2, does IO at 2 and runs again at 3, B arrives at 1 and runs at 2.
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 1), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B, the second".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 1)], 1),
+        ];
+        let log = Simulation::with_policy(processes, Policy::Fcfs).run();
+        let mut out = vec![];
+        write_metrics(&log.content, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "pid,name,priority,arrival,first_run,completion,turnaround,wait,response,cpu_time,io_time,preemptions");
+        assert_eq!(lines[1], "1,\"B, the second\",2,1,2,3,2,1,1,1,0,0");
+        assert_eq!(parse_row(lines[2]), ["0", "A", "1", "0", "0", "4", "4", "0", "0", "3", "1", "0"]);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::{log::TickEntry, metrics, scheduler::SchedulerResult};
+
+// Plain text Gantt chart, one character per tick. Every process gets its own symbol
+// since names aren't unique (the sample input is six processes all called foo).
+
+const SYMBOLS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+pub const IDLE: char = '.';
+// how many ticks fit on one row before we wrap.
+const WIDTH: usize = 100;
+const LABEL_WIDTH: usize = 6;
+
+fn lane(content: &[TickEntry], symbols: &HashMap<i32, char>, device: impl Fn(&TickEntry) -> &SchedulerResult) -> Vec<char> {
+    content
+        .iter()
+        .map(|entry| match device(entry) {
+            SchedulerResult::Finished(p) | SchedulerResult::Processing(p) => symbols.get(&p.pid).copied().unwrap_or('#'),
+            _ => IDLE,
+        })
+        .collect()
+}
+
+/// The symbol each pid is drawn with, in pid order, `#` once they run out.
+pub fn symbols(content: &[TickEntry]) -> HashMap<i32, char> {
+    let mut pids = metrics::processes(content).into_iter().map(|proc| proc.pid).collect::<Vec<_>>();
+    pids.sort();
+    pids.into_iter()
+        .zip(SYMBOLS.iter().map(|&c| c as char).chain(std::iter::repeat('#')))
+        .collect()
+}
+
+pub fn render(content: &[TickEntry]) -> String {
+    let mut processes = metrics::processes(content);
+    processes.sort_by_key(|proc| proc.pid);
+    let symbols = symbols(content);
+
+    let lanes = [
+        ("CPU0", lane(content, &symbols, |entry| &entry.cpu_process)),
+        ("IO0", lane(content, &symbols, |entry| &entry.io_process)),
+    ];
+
+    let mut out = String::new();
+    for start in (0..content.len()).step_by(WIDTH) {
+        let end = (start + WIDTH).min(content.len());
+
+        // time axis, a number and a marker every 10 ticks.
+        let mut numbers = vec![' '; end - start + 8];
+        let mut marks = vec![' '; end - start];
+        for time in (start..end).filter(|time| time % 10 == 0) {
+            marks[time - start] = '|';
+            for (i, digit) in time.to_string().chars().enumerate() {
+                numbers[time - start + i] = digit;
+            }
+        }
+        let numbers = numbers.into_iter().collect::<String>();
+        let marks = marks.into_iter().collect::<String>();
+        out.push_str(&format!("{:LABEL_WIDTH$}{}\n", "", numbers.trim_end()));
+        out.push_str(&format!("{:LABEL_WIDTH$}{}\n", "", marks.trim_end()));
+        for (name, ticks) in &lanes {
+            out.push_str(&format!("{:LABEL_WIDTH$}{}\n", name, ticks[start..end].iter().collect::<String>()));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("{} = idle\n", IDLE));
+    for proc in &processes {
+        out.push_str(&format!("{} = {} (pid {})\n", symbols[&proc.pid], proc.name, proc.pid));
+    }
+    out
+}
+
+pub fn write_gantt(content: &[TickEntry], out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "{}", render(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    #[test]
+    fn test_render() {
+        let processes = vec![
+            Process::new("long".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 60), Burst(BurstKind::Io, 10), Burst(BurstKind::Cpu, 50)], 0),
+            Process::new("short".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 5)], 0),
+        ];
+        // long runs 0-60, short 60-65 while long does IO 60-70, then long runs 70-120.
+        let log = Simulation::with_policy(processes, Policy::Fcfs).run();
+        let gantt = render(&log.content);
+        let lines = gantt.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5 + 5 + 3);
+
+        assert!(lines[0].starts_with("      0         10        20"));
+        assert!(lines[0].ends_with("90"));
+        assert_eq!(lines[1], format!("      {}|", "|         ".repeat(9)));
+        let cpu = format!("{}{}{}{}", "0".repeat(60), "1".repeat(5), ".".repeat(5), "0".repeat(30));
+        assert_eq!(lines[2], format!("CPU0  {}", cpu));
+        assert_eq!(lines[3], format!("IO0   {}{}{}", ".".repeat(60), "0".repeat(10), ".".repeat(30)));
+        assert_eq!(lines[4], "");
+
+        // the last 20 ticks wrap onto a second block numbered from 100.
+        assert_eq!(lines[5], "      100       110");
+        assert_eq!(lines[7], format!("CPU0  {}", "0".repeat(20)));
+        assert_eq!(lines[8], format!("IO0   {}", ".".repeat(20)));
+
+        assert_eq!(lines[10..], [". = idle", "0 = long (pid 0)", "1 = short (pid 1)"]);
+    }
+}
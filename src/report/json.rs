@@ -0,0 +1,178 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::{
+    analysis::fairness::{self, Fairness},
+    log::TickEntry,
+    metrics::{self, ProcessMetrics, Summary},
+    process::Process,
+    scheduler::SchedulerResult,
+    stats::{self, Statistics},
+};
+
+// TickEntry mirrors what the simulator needs rather than what's nice to read,
+// so the exported shape is spelled out separately here.
+
+#[derive(Serialize)]
+struct Device<'a> {
+    // "processing", "finished" or "idle"
+    state: &'static str,
+    process: Option<&'a Process>,
+}
+
+impl<'a> From<&'a SchedulerResult> for Device<'a> {
+    fn from(result: &'a SchedulerResult) -> Self {
+        match result {
+            SchedulerResult::Processing(p) => Device { state: "processing", process: Some(p) },
+            SchedulerResult::Finished(p) => Device { state: "finished", process: Some(p) },
+            _ => Device { state: "idle", process: None },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Tick<'a> {
+    time: usize,
+    cpu: Device<'a>,
+    io: Device<'a>,
+    cpu_queue: &'a [Process],
+    io_queue: &'a [Process],
+    finished: Vec<i32>,
+    yet_to_arrive: Vec<i32>,
+}
+
+impl<'a> Tick<'a> {
+    fn new(time: usize, entry: &'a TickEntry) -> Self {
+        Self {
+            time,
+            cpu: (&entry.cpu_process).into(),
+            io: (&entry.io_process).into(),
+            cpu_queue: &entry.cpu_queue,
+            io_queue: &entry.io_queue,
+            finished: entry.finished_processes.iter().map(|proc| proc.pid).collect(),
+            yet_to_arrive: entry.yet_to_arrive.iter().map(|proc| proc.pid).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    summary: Summary,
+    statistics: Statistics,
+    fairness: Fairness,
+    processes: Vec<ProcessMetrics>,
+    ticks: Vec<Tick<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Line<'a> {
+    Tick(Tick<'a>),
+    Process(ProcessMetrics),
+    Summary(Summary),
+    Statistics(Statistics),
+    Fairness(Fairness),
+}
+
+/// One JSON document with the summary, statistics, fairness, per-process metrics and every tick.
+pub fn write_json(content: &[TickEntry], starvation_threshold: i32, out: &mut dyn Write) -> io::Result<()> {
+    let processes = metrics::process_metrics(content);
+    let run = Run {
+        summary: metrics::summary(content),
+        statistics: stats::statistics(&processes),
+        fairness: fairness::fairness(content, starvation_threshold),
+        processes,
+        ticks: content.iter().enumerate().map(|(time, entry)| Tick::new(time, entry)).collect(),
+    };
+    serde_json::to_writer_pretty(&mut *out, &run)?;
+    writeln!(out)
+}
+
+/// One object per line, tagged with a `type` of `tick`, `process`, `summary`, `statistics` or `fairness`.
+/// Ticks come first, in order, then the processes, and the summary, statistics and fairness last.
+pub fn write_json_lines(content: &[TickEntry], starvation_threshold: i32, out: &mut dyn Write) -> io::Result<()> {
+    let metrics = metrics::process_metrics(content);
+    let statistics = stats::statistics(&metrics);
+    let ticks = content.iter().enumerate().map(|(time, entry)| Line::Tick(Tick::new(time, entry)));
+    let processes = metrics.into_iter().map(Line::Process);
+    let totals = [
+        Line::Summary(metrics::summary(content)),
+        Line::Statistics(statistics),
+        Line::Fairness(fairness::fairness(content, starvation_threshold)),
+    ];
+    for line in ticks.chain(processes).chain(totals) {
+        serde_json::to_writer(&mut *out, &line)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    use crate::{
+        engine::Simulation,
+        log::Log,
+        process::{Burst, BurstKind},
+        scheduler::Policy,
+    };
+
+    fn log() -> Log {
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 1), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 1)], 1),
+        ];
+        Simulation::with_policy(processes, Policy::Fcfs).run()
+    }
+
+    #[test]
+    fn test_json() {
+        let log = log();
+        let mut out = vec![];
+        write_json(&log.content, 50, &mut out).unwrap();
+        let run: Value = serde_json::from_slice(&out).unwrap();
+
+        let ticks = run["ticks"].as_array().unwrap();
+        assert_eq!(ticks.len(), log.content.len());
+        assert_eq!(ticks[0]["time"], 0);
+        assert_eq!(ticks[0]["cpu"]["state"], "processing");
+        assert_eq!(ticks[0]["cpu"]["process"]["name"], "A");
+        assert_eq!(ticks[0]["cpu"]["process"]["burst"][0], serde_json::json!(["cpu", 1]));
+        assert_eq!(ticks[0]["io"], serde_json::json!({"state": "idle", "process": null}));
+        assert_eq!(ticks[0]["yet_to_arrive"], serde_json::json!([1]));
+        assert_eq!(ticks.last().unwrap()["finished"], serde_json::json!([1, 0]));
+
+        let processes = run["processes"].as_array().unwrap();
+        // in the order they finished.
+        assert_eq!(processes.iter().map(|proc| proc["name"].as_str().unwrap()).collect::<Vec<_>>(), ["B", "A"]);
+        assert_eq!(processes[0]["turnaround"], 2);
+        // the last tick, not how many there were.
+        assert_eq!(run["summary"]["time"], log.content.len() - 1);
+        assert!(run["statistics"].is_object());
+        assert_eq!(run["fairness"]["starvation_threshold"], 50);
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let log = log();
+        let mut out = vec![];
+        write_json_lines(&log.content, 50, &mut out).unwrap();
+        let lines = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        let types = lines.iter().map(|line| line["type"].as_str().unwrap()).collect::<Vec<_>>();
+        let ticks = log.content.len();
+        assert_eq!(types.len(), ticks + 2 + 3);
+        assert!(types[..ticks].iter().all(|&kind| kind == "tick"));
+        assert_eq!(types[ticks..], ["process", "process", "summary", "statistics", "fairness"]);
+        // the tag sits next to the fields rather than wrapping them.
+        assert_eq!(lines[1]["time"], 1);
+        assert_eq!(lines[ticks]["name"], "B");
+        assert_eq!(lines[ticks + 2]["time"], ticks - 1);
+    }
+}
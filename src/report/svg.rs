@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::{log::TickEntry, metrics};
+
+use super::{markers, segments, Lane, MarkerKind};
+
+const TICK_WIDTH: usize = 14;
+const LABEL_WIDTH: usize = 50;
+const LANE_HEIGHT: usize = 28;
+// the row above the lanes where arrivals and completions are drawn.
+const MARKER_ROW: usize = 34;
+const AXIS_HEIGHT: usize = 22;
+const LEGEND_ROW: usize = 18;
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A colour per pid, spread around the hue wheel so neighbouring pids don't look alike.
+pub fn colors(content: &[TickEntry]) -> HashMap<i32, String> {
+    let mut pids = metrics::processes(content).into_iter().map(|proc| proc.pid).collect::<Vec<_>>();
+    pids.sort();
+    pids.into_iter()
+        .enumerate()
+        .map(|(i, pid)| (pid, format!("hsl({}, 65%, 55%)", (i * 137) % 360)))
+        .collect()
+}
+
+fn lane_y(lane: Lane) -> usize {
+    match lane {
+        Lane::Cpu => AXIS_HEIGHT + MARKER_ROW,
+        Lane::Io => AXIS_HEIGHT + MARKER_ROW + LANE_HEIGHT + 8,
+    }
+}
+
+fn tick_x(time: usize) -> usize {
+    LABEL_WIDTH + time * TICK_WIDTH
+}
+
+pub fn render(content: &[TickEntry]) -> String {
+    let colors = colors(content);
+    let mut processes = metrics::processes(content);
+    processes.sort_by_key(|proc| proc.pid);
+
+    let chart_height = lane_y(Lane::Io) + LANE_HEIGHT + 12;
+    let width = tick_x(content.len()) + 20;
+    let height = chart_height + LEGEND_ROW * processes.len() + 10;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"monospace\" font-size=\"11\">\n",
+        w = width,
+        h = height,
+    );
+    svg.push_str(&format!("<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n", width, height));
+
+    // time axis, a label every 5 ticks and a faint grid line every tick.
+    for time in 0..=content.len() {
+        let x = tick_x(time);
+        svg.push_str(&format!(
+            "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"#ddd\"/>\n",
+            AXIS_HEIGHT - 4,
+            chart_height - 8,
+        ));
+        if time % 5 == 0 {
+            svg.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n", x, AXIS_HEIGHT - 8, time));
+        }
+    }
+
+    for (lane, label) in [(Lane::Cpu, "CPU0"), (Lane::Io, "IO0")] {
+        svg.push_str(&format!(
+            "<text x=\"4\" y=\"{}\">{}</text>\n",
+            lane_y(lane) + LANE_HEIGHT / 2 + 4,
+            label
+        ));
+    }
+
+    for segment in segments(content) {
+        let kind = match segment.lane {
+            Lane::Cpu => "CPU",
+            Lane::Io => "IO",
+        };
+        let ending = if segment.burst_finished { "burst finished" } else { "preempted" };
+        svg.push_str(&format!(
+            "<g><title>{} (pid {})\n{} burst, T{}-T{} ({} ticks), {}\nremaining bursts: {:?}</title>\
+             <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"black\" stroke-width=\"0.5\"/>\
+             <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text></g>\n",
+            escape(&segment.name),
+            segment.pid,
+            kind,
+            segment.start,
+            segment.end,
+            segment.end - segment.start,
+            ending,
+            segment.remaining,
+            tick_x(segment.start),
+            lane_y(segment.lane),
+            (segment.end - segment.start) * TICK_WIDTH,
+            LANE_HEIGHT,
+            colors[&segment.pid],
+            (tick_x(segment.start) + tick_x(segment.end)) / 2,
+            lane_y(segment.lane) + LANE_HEIGHT / 2 + 4,
+            segment.pid,
+        ));
+    }
+
+    for marker in markers(content) {
+        let x = tick_x(marker.time);
+        let title = match marker.kind {
+            MarkerKind::Arrival => "arrived",
+            MarkerKind::Preemption => "preempted",
+            MarkerKind::Completion => "completed",
+        };
+        let title = format!("<title>T{}: {} (pid {}) {}</title>", marker.time, escape(&marker.name), marker.pid, title);
+        let shape = match marker.kind {
+            // arrivals point down into the lanes, completions are a dot.
+            MarkerKind::Arrival => {
+                let y = AXIS_HEIGHT + 8;
+                format!(
+                    "<polygon points=\"{},{} {},{} {},{}\" fill=\"seagreen\"/>",
+                    x - 5, y, x + 5, y, x, y + 10
+                )
+            }
+            MarkerKind::Completion => format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"5\" fill=\"black\"/>",
+                x,
+                AXIS_HEIGHT + MARKER_ROW - 12
+            ),
+            MarkerKind::Preemption => format!(
+                "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"crimson\" stroke-width=\"3\"/>",
+                lane_y(Lane::Cpu) - 4,
+                lane_y(Lane::Cpu) + LANE_HEIGHT + 4,
+            ),
+        };
+        svg.push_str(&format!("<g>{}{}</g>\n", title, shape));
+    }
+
+    for (i, proc) in processes.iter().enumerate() {
+        let y = chart_height + i * LEGEND_ROW;
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{}\" stroke=\"black\" stroke-width=\"0.5\"/>\
+             <text x=\"{}\" y=\"{}\">{} = {}</text>\n",
+            LABEL_WIDTH,
+            y,
+            colors[&proc.pid],
+            LABEL_WIDTH + 18,
+            y + 10,
+            proc.pid,
+            escape(&proc.name),
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn write_svg(content: &[TickEntry], out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "{}", render(content))
+}
+
+/// A standalone page with the chart and the summary / per-process metrics under it.
+pub fn write_html(content: &[TickEntry], out: &mut dyn Write) -> io::Result<()> {
+    let summary = metrics::summary(content);
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html><head><meta charset=\"utf-8\"><title>Schedule</title>")?;
+    writeln!(
+        out,
+        "<style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+         td, th {{ border: 1px solid #999; padding: 2px 8px; text-align: right; }} \
+         .chart {{ overflow-x: auto; }}</style>"
+    )?;
+    writeln!(out, "</head><body>")?;
+    writeln!(out, "<div class=\"chart\">\n{}</div>", render(content))?;
+
+    writeln!(out, "<h2>System state</h2>\n<table>")?;
+    for (name, value) in [
+        ("Time", summary.time.to_string()),
+        ("CPU usage", format!("{:.2}", summary.cpu_usage)),
+        ("IO usage", format!("{:.2}", summary.io_usage)),
+        ("Avg wait", format!("{:.2}", summary.avg_wait)),
+        ("Avg turnaround", format!("{:.2}", summary.avg_turnaround)),
+        ("Avg response", format!("{:.2}", summary.avg_response)),
+        ("Throughput", format!("{:.2}", summary.throughput)),
+    ] {
+        writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", name, value)?;
+    }
+    writeln!(out, "</table>")?;
+
+    writeln!(out, "<h2>Processes</h2>\n<table>")?;
+    writeln!(
+        out,
+        "<tr><th>pid</th><th>name</th><th>priority</th><th>arrival</th><th>first run</th><th>completion</th>\
+         <th>turnaround</th><th>wait</th><th>response</th><th>cpu time</th><th>io time</th><th>preemptions</th></tr>"
+    )?;
+    for m in metrics::process_metrics(content) {
+        writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+             <td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            m.pid, escape(&m.name), m.priority, m.arrival, m.first_run, m.completion,
+            m.turnaround, m.wait, m.response, m.cpu_time, m.io_time, m.preemptions,
+        )?;
+    }
+    writeln!(out, "</table>\n</body></html>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a < b && \"c\" > d"), "a &lt; b &amp;&amp; &quot;c&quot; &gt; d");
+        // the ampersands of the escapes themselves aren't escaped again.
+        assert_eq!(escape("&lt;"), "&amp;lt;");
+    }
+
+    #[test]
+    fn test_render() {
+        let processes = vec![
+            Process::new("<A & co>".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 3)], 1),
+        ];
+        let log = Simulation::with_policy(processes, Policy::RoundRobin { quantum: 2 }).run();
+        let svg = render(&log.content);
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert!(!svg.contains("<A & co>") && svg.contains("&lt;A &amp; co&gt;"));
+
+        // a group per segment with its box and its label, then one per marker.
+        let segments = segments(&log.content);
+        // segment titles run over several lines, so split on the groups instead.
+        let groups = svg.split("<g>").skip(1).map(|group| group.split("</g>").next().unwrap()).collect::<Vec<_>>();
+        let (segment_groups, marker_groups): (Vec<&str>, Vec<&str>) = groups.into_iter().partition(|group| group.contains(" burst, T"));
+        assert_eq!(segment_groups.len(), segments.len());
+        for group in &segment_groups {
+            assert_eq!((group.matches("<rect ").count(), group.matches("<text ").count()), (1, 1));
+        }
+        let markers = markers(&log.content);
+        assert_eq!(marker_groups.len(), markers.len());
+        assert_eq!(svg.matches("<polygon ").count(), markers.iter().filter(|m| m.kind == MarkerKind::Arrival).count());
+        assert_eq!(svg.matches("<circle ").count(), 2);
+
+        let mut html = vec![];
+        write_html(&log.content, &mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert_eq!(html.matches("<table>").count(), 2);
+        // a row per process, the headers being all <th>.
+        assert_eq!(html.matches("<tr><td>").count(), 2);
+        assert!(html.contains("<tr><th>Time</th><td>"));
+    }
+}
@@ -0,0 +1,200 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    engine::{Edit, Simulation},
+    log::Log,
+    process::Process,
+    scheduler::Policy,
+};
+
+// A finished run written out in full with `--save`, so it can be opened again with
+// `--open` and looked at or exported without simulating anything. Everything needed to
+// simulate it again is in there too.
+
+/// Bumped whenever a saved run's layout changes in a way older versions can't read.
+pub const FORMAT: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedRun {
+    pub format: u32,
+    // the version of this program that saved it.
+    pub version: String,
+    // the processes as they were read in, before any of them ran.
+    pub workload: Vec<Process>,
+    // the scheduler it started with, a live run may have switched since.
+    pub scheduler: Policy,
+    pub live: bool,
+    // what was changed on the way in a live run, so it can still be simulated again from
+    // the workload and scheduler. Empty otherwise.
+    pub edits: Vec<Edit>,
+    // the starvation threshold and window are kept with the ticks.
+    pub log: Log,
+}
+
+// just enough to tell whether the rest can be read.
+#[derive(Deserialize)]
+struct Header {
+    format: u32,
+    version: String,
+}
+
+impl SavedRun {
+    pub fn new(workload: Vec<Process>, scheduler: Policy, live: bool, edits: Vec<Edit>, log: Log) -> Self {
+        Self {
+            format: FORMAT,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            workload,
+            scheduler,
+            live,
+            edits,
+            log,
+        }
+    }
+
+    /// Simulates the run again from its inputs, making each edit before the tick it was
+    /// made before, for as many ticks as were saved.
+    pub fn replay(&self) -> Log {
+        let mut sim = Simulation::with_policy(self.workload.clone(), self.scheduler);
+        sim.log.starvation_threshold = self.log.starvation_threshold;
+        sim.log.window = self.log.window;
+        let mut edits = self.edits.iter().peekable();
+        while sim.log.content.len() < self.log.content.len() {
+            while let Some(edit) = edits.next_if(|edit| edit.tick == sim.time()) {
+                sim.apply(edit.change.clone());
+            }
+            sim.step();
+        }
+        sim.log
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let header: Header = serde_json::from_str(json).map_err(|e| format!("not a saved run: {}", e))?;
+        if header.format != FORMAT {
+            return Err(format!(
+                "saved by version {} in format {}, this version only reads format {}",
+                header.version, header.format, FORMAT
+            ));
+        }
+        let saved: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if saved.log.content.is_empty() {
+            return Err("the saved run has no ticks".to_string());
+        }
+        // everything that shows or exports it goes by the log, so it has to be the run
+        // the rest of the file describes.
+        let ticks = |log: &Log| serde_json::to_string(&log.content).unwrap();
+        if ticks(&saved.replay()) != ticks(&saved.log) {
+            return Err("the saved ticks aren't what its workload, scheduler and edits give".to_string());
+        }
+        Ok(saved)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::parse(&json)
+    }
+
+    pub fn write(&self, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer(&mut *out, self)?;
+        writeln!(out)
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let mut f = std::fs::File::create(path)?;
+        self.write(&mut f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Change,
+        process::{Burst, BurstKind},
+    };
+
+    #[test]
+    fn test_round_trip() {
+        let workload = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 3), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 2)], 1),
+        ];
+        let policy = Policy::RoundRobin { quantum: 2 };
+        let simulate = || {
+            let mut sim = Simulation::with_policy(workload.clone(), policy);
+            sim.log.starvation_threshold = 3;
+            sim.run()
+        };
+        let saved = SavedRun::new(workload.clone(), policy, false, vec![], simulate());
+        let json = to_json(&saved);
+        assert!(json.contains("\"scheduler\":\"rr:2\""));
+
+        let loaded = SavedRun::parse(&json).unwrap();
+        assert_eq!((&loaded.workload, loaded.scheduler, loaded.live), (&workload, policy, false));
+        assert_eq!((loaded.log.starvation_threshold, loaded.log.window), (3, saved.log.window));
+        // the same log, which is the one simulating it again gives.
+        let text = |log: &Log| Log::get_log_content(&log.content, log.starvation_threshold);
+        assert_eq!(text(&loaded.log), text(&saved.log));
+        assert_eq!(text(&loaded.log), text(&simulate()));
+        assert_eq!(text(&loaded.replay()), text(&simulate()));
+
+        let newer = json.replacen("\"format\":1", "\"format\":2", 1);
+        assert_eq!(
+            SavedRun::parse(&newer).err(),
+            Some(format!("saved by version {} in format 2, this version only reads format 1", env!("CARGO_PKG_VERSION")))
+        );
+        assert!(SavedRun::parse("A 0 1 3").err().unwrap().starts_with("not a saved run"));
+    }
+
+    fn to_json(saved: &SavedRun) -> String {
+        let mut json = vec![];
+        saved.write(&mut json).unwrap();
+        String::from_utf8(json).unwrap()
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let workload = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 3)], 0),
+            Process::new("B".to_string(), 1, 0, vec![Burst(BurstKind::Cpu, 2)], 1),
+        ];
+        let log = || Simulation::with_policy(workload.clone(), Policy::Fcfs).run();
+        let empty = SavedRun::new(workload.clone(), Policy::Fcfs, false, vec![], Log::new());
+        assert_eq!(SavedRun::parse(&to_json(&empty)).err(), Some("the saved run has no ticks".to_string()));
+
+        let mismatch = "the saved ticks aren't what its workload, scheduler and edits give".to_string();
+        let missing = SavedRun::new(workload[..1].to_vec(), Policy::Fcfs, false, vec![], log());
+        assert_eq!(SavedRun::parse(&to_json(&missing)).err(), Some(mismatch.clone()));
+        // B would have cut in ahead of A.
+        let other_policy = SavedRun::new(workload.clone(), Policy::Priority, false, vec![], log());
+        assert_eq!(SavedRun::parse(&to_json(&other_policy)).err(), Some(mismatch));
+    }
+
+    #[test]
+    fn test_live_round_trip() {
+        let workload = vec![
+            Process::new("A".to_string(), 0, 3, vec![Burst(BurstKind::Cpu, 4)], 0),
+            Process::new("B".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 3)], 0),
+        ];
+        let mut sim = Simulation::with_policy(workload.clone(), Policy::Fcfs);
+        sim.step();
+        let pid = sim.next_pid();
+        sim.inject(Process::new("C".to_string(), pid, 0, vec![Burst(BurstKind::Cpu, 1)], 0));
+        sim.step();
+        sim.set_policy(Policy::Priority);
+        let edits = sim.edits().to_vec();
+        let log = sim.run();
+        let saved = SavedRun::new(workload.clone(), Policy::Fcfs, true, edits, log);
+
+        let loaded = SavedRun::parse(&to_json(&saved)).unwrap();
+        assert_eq!((&loaded.workload, loaded.scheduler, loaded.live), (&workload, Policy::Fcfs, true));
+        let changes = loaded.edits.iter().map(|edit| (edit.tick, edit.change.clone())).collect::<Vec<_>>();
+        assert_eq!(changes[1], (2, Change::SetPolicy(Policy::Priority)));
+        assert!(matches!(&changes[0], (1, Change::Inject(proc)) if proc.name == "C"));
+        // C would be last under FCFS, it only runs at once because of the switch to priority.
+        let text = |log: &Log| Log::get_log_content(&log.content, log.starvation_threshold);
+        assert_eq!(text(&loaded.replay()), text(&saved.log));
+        assert_eq!(crate::metrics::completion_time(pid, &loaded.replay().content), Some(3));
+    }
+}
@@ -1,12 +1,17 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
 use crate::system_state::SystemState;
-use crate::process::Process;
+use crate::process::{BurstKind, Process};
 
 
 pub mod fcfs;
 pub mod priority;
 pub mod round_robin;
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub enum SchedulerResult {
     Finished(Process),
     // remaining burst
@@ -21,5 +26,89 @@ pub trait Scheduler {
     fn tick(&mut self, system_state: &SystemState) -> SchedulerResult;
     fn enqueue(&mut self, proc: Process);
     fn get_queue(&self) -> Vec<&Process>;
+    // takes a process out wherever it is in the queue, including off the device.
+    fn remove(&mut self, pid: i32) -> Option<Process>;
+    // false if the process isn't in this scheduler.
+    fn set_priority(&mut self, pid: i32, priority: i32) -> bool;
+    // a copy of everything it's holding and where it's up to, e.g. whose turn it is,
+    // so a run can be put back to this point later.
+    fn snapshot(&self) -> Box<dyn Scheduler>;
+    // empties it, returning everything in the order it would have got the device.
+    fn drain(&mut self) -> Vec<Process>;
+}
+
+/// Which CPU scheduler to run, along with whatever knobs it has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Policy {
+    Fcfs,
+    Priority,
+    RoundRobin { quantum: i32 },
+}
+
+impl Policy {
+    pub fn cpu_scheduler(self) -> Box<dyn Scheduler> {
+        match self {
+            Policy::Fcfs => Box::new(fcfs::FCFS::new(vec![], BurstKind::Cpu)),
+            Policy::Priority => Box::new(priority::Priority::new(vec![], BurstKind::Cpu)),
+            Policy::RoundRobin { quantum } => Box::new(round_robin::RoundRobin::new(vec![], BurstKind::Cpu, quantum)),
+        }
+    }
+}
+
+impl Policy {
+    /// The names of the knobs `--sweep` can turn.
+    pub const PARAMETERS: &'static [&'static str] = &["quantum"];
+
+    /// The policy that `param` belongs to, with it set to `value`.
+    pub fn with_parameter(param: &str, value: i32) -> Result<Policy, String> {
+        match param {
+            "quantum" if value > 0 => Ok(Policy::RoundRobin { quantum: value }),
+            "quantum" => Err(format!("bad quantum {}", value)),
+            _ => Err(format!("unknown parameter {:?}, expected one of {:?}", param, Self::PARAMETERS)),
+        }
+    }
 }
 
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Policy::Fcfs => write!(f, "fcfs"),
+            Policy::Priority => write!(f, "priority"),
+            Policy::RoundRobin { quantum } => write!(f, "rr:{}", quantum),
+        }
+    }
+}
+
+// the inverse of Display, so `rr:4` is round robin with a quantum of 4.
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "fcfs" => Ok(Policy::Fcfs),
+            None if s == "priority" => Ok(Policy::Priority),
+            Some(("rr", quantum)) => match quantum.parse() {
+                Ok(quantum) if quantum > 0 => Ok(Policy::RoundRobin { quantum }),
+                _ => Err(format!("bad quantum {:?}", quantum)),
+            },
+            None if s == "rr" => Err("rr needs a quantum, e.g. rr:4".to_string()),
+            _ => Err(format!("unknown scheduler {:?}", s)),
+        }
+    }
+}
+
+// written out the way it's given on the command line.
+impl From<Policy> for String {
+    fn from(policy: Policy) -> Self {
+        policy.to_string()
+    }
+}
+
+impl TryFrom<String> for Policy {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+    }
+}
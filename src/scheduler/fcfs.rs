@@ -4,6 +4,8 @@ use crate::{process::{Process, BurstKind, Burst}, system_state::SystemState};
 
 use super::{Scheduler, SchedulerResult};
 
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone)]
 pub struct FCFS {
     // FCFS is a FIFO algorithm. It takes processes by arrival time,
     // and processes the ones that came in first. A VecDeque,
@@ -15,7 +17,7 @@ pub struct FCFS {
 
 impl FCFS {
     pub fn new(mut processes: Vec<Process>, kind: BurstKind) -> Self {
-        processes.sort_by(|a,b| a.arrival.cmp(&b.arrival));
+        processes.sort_by_key(|a| a.arrival);
         Self {
             processes: processes.into(),
             kind,
@@ -57,6 +59,30 @@ impl Scheduler for FCFS {
     fn enqueue(&mut self, proc: Process) {
         self.processes.push_back(proc);
     }
+
+    fn remove(&mut self, pid: i32) -> Option<Process> {
+        let index = self.processes.iter().position(|proc| proc.pid == pid)?;
+        self.processes.remove(index)
+    }
+
+    fn snapshot(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn drain(&mut self) -> Vec<Process> {
+        self.processes.drain(..).collect()
+    }
+
+    fn set_priority(&mut self, pid: i32, priority: i32) -> bool {
+        // FCFS doesn't care, but the process should still say what it was changed to.
+        match self.processes.iter_mut().find(|proc| proc.pid == pid) {
+            Some(proc) => {
+                proc.priority = priority;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 #[cfg(test)]
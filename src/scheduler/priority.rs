@@ -5,14 +5,14 @@ use crate::{process::{Process, BurstKind, Burst}, system_state::SystemState};
 
 use super::{Scheduler, SchedulerResult};
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 struct PriorityProcess {
     process: Process
 }
 
 impl PartialOrd for PriorityProcess {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other.process.priority.partial_cmp(&self.process.priority)
+        Some(self.cmp(other))
     }
 }
 impl Ord for PriorityProcess {
@@ -21,6 +21,7 @@ impl Ord for PriorityProcess {
     }
 }
 
+#[derive(Clone)]
 pub struct Priority {
     // FCFS is a FIFO algorithm. It takes processes by arrival time,
     // and processes the ones that came in first. A VecDeque,
@@ -70,4 +71,36 @@ impl Scheduler for Priority {
     fn enqueue(&mut self, proc: Process) {
         self.processes.push(PriorityProcess { process: proc })
     }
+
+    fn remove(&mut self, pid: i32) -> Option<Process> {
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.processes)
+            .into_iter()
+            .partition(|proc| proc.process.pid == pid);
+        self.processes = kept.into();
+        removed.into_iter().next().map(|proc| proc.process)
+    }
+
+    fn snapshot(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn drain(&mut self) -> Vec<Process> {
+        // ascending by how the heap orders them, so the one that'd be popped first is last.
+        let mut processes = std::mem::take(&mut self.processes).into_sorted_vec();
+        processes.reverse();
+        processes.into_iter().map(|proc| proc.process).collect()
+    }
+
+    fn set_priority(&mut self, pid: i32, priority: i32) -> bool {
+        // the heap has to be rebuilt around the new priority, which taking it out and
+        // putting it back in does.
+        match self.remove(pid) {
+            Some(mut proc) => {
+                proc.priority = priority;
+                self.enqueue(proc);
+                true
+            }
+            None => false,
+        }
+    }
 }
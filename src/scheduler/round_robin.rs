@@ -2,6 +2,7 @@ use crate::process::{Process, BurstKind, Burst};
 
 use super::{Scheduler, SchedulerResult};
 
+#[derive(Clone)]
 pub struct RoundRobin {
     quantum_time: i32,
     // I couldn't find a good premade RingBuffer that I liked
@@ -74,4 +75,98 @@ impl Scheduler for RoundRobin {
     fn get_queue(&self) -> Vec<&Process> {
         self.processes.iter().collect()
     }
+
+    fn remove(&mut self, pid: i32) -> Option<Process> {
+        let index = self.processes.iter().position(|proc| proc.pid == pid)?;
+        let current = self.index % self.processes.len();
+        // keep pointing at the same process, or at the one after it if it's the one going.
+        if index < current {
+            self.index = current - 1;
+        } else {
+            self.index = current;
+        }
+        if index == current {
+            self.remaining_time = self.quantum_time;
+        }
+        Some(self.processes.remove(index))
+    }
+
+    fn snapshot(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn drain(&mut self) -> Vec<Process> {
+        // starting with whoever's turn it is.
+        if !self.processes.is_empty() {
+            let current = self.index % self.processes.len();
+            self.processes.rotate_left(current);
+        }
+        self.index = 0;
+        self.remaining_time = self.quantum_time;
+        std::mem::take(&mut self.processes)
+    }
+
+    fn set_priority(&mut self, pid: i32, priority: i32) -> bool {
+        match self.processes.iter_mut().find(|proc| proc.pid == pid) {
+            Some(proc) => {
+                proc.priority = priority;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_state::SystemState;
+
+    fn running(result: SchedulerResult) -> i32 {
+        match result {
+            SchedulerResult::Processing(p) | SchedulerResult::Finished(p) => p.pid,
+            other => panic!("expected a process, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_remove_keeps_turn() {
+        let state = SystemState::new();
+        let mut sched = RoundRobin::new(
+            (0..3).map(|pid| Process::new(format!("P{}", pid), pid, 0, vec![Burst(BurstKind::Cpu, 10)], 0)).collect(),
+            BurstKind::Cpu,
+            2,
+        );
+        // P0 for 2, then P1 has had 1 of its 2.
+        for _ in 0..3 {
+            sched.tick(&state);
+        }
+        // taking out a process before P1 mustn't hand the rest of its quantum to P2.
+        assert_eq!(sched.remove(0).map(|proc| proc.pid), Some(0));
+        assert_eq!(running(sched.tick(&state)), 1);
+        assert_eq!(running(sched.tick(&state)), 2);
+        // taking out the running process gives the next one a fresh quantum.
+        assert!(sched.remove(2).is_some());
+        assert_eq!(running(sched.tick(&state)), 1);
+        assert_eq!(running(sched.tick(&state)), 1);
+        assert!(sched.remove(5).is_none());
+    }
+
+    #[test]
+    fn test_drain_from_turn() {
+        let state = SystemState::new();
+        let mut sched = RoundRobin::new(
+            (0..3).map(|pid| Process::new(format!("P{}", pid), pid, 0, vec![Burst(BurstKind::Cpu, 10)], 0)).collect(),
+            BurstKind::Cpu,
+            2,
+        );
+        for _ in 0..3 {
+            sched.tick(&state);
+        }
+        let copy = sched.snapshot();
+        // P1 is partway through its turn so it goes first, and P0 has had its go.
+        assert_eq!(sched.drain().iter().map(|proc| proc.pid).collect::<Vec<_>>(), vec![1, 2, 0]);
+        assert!(sched.get_queue().is_empty());
+        assert_eq!(copy.get_queue().len(), 3);
+    }
 }
@@ -0,0 +1,151 @@
+use serde::Serialize;
+
+use crate::metrics::ProcessMetrics;
+
+/// The spread of one metric over a set of processes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Distribution {
+    pub min: f64,
+    pub mean: f64,
+    pub median: f64,
+    // nearest-rank, so always one of the values.
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.
+        } else {
+            sorted[n / 2]
+        };
+        let p95_rank = (0.95 * n as f64).ceil() as usize;
+        Some(Self {
+            min: sorted[0],
+            mean: sorted.iter().sum::<f64>() / n as f64,
+            median,
+            p95: sorted[p95_rank.max(1) - 1],
+            max: sorted[n - 1],
+        })
+    }
+}
+
+/// A `Distribution` for every per-process metric. They're `None` when there's
+/// no finished process to compute them over.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Statistics {
+    pub count: usize,
+    pub wait: Option<Distribution>,
+    pub turnaround: Option<Distribution>,
+    pub response: Option<Distribution>,
+    pub cpu_time: Option<Distribution>,
+    pub io_time: Option<Distribution>,
+    pub preemptions: Option<Distribution>,
+}
+
+pub fn statistics(metrics: &[ProcessMetrics]) -> Statistics {
+    let of = |metric: fn(&ProcessMetrics) -> i32| {
+        Distribution::of(&metrics.iter().map(|m| metric(m) as f64).collect::<Vec<_>>())
+    };
+    Statistics {
+        count: metrics.len(),
+        wait: of(|m| m.wait),
+        turnaround: of(|m| m.turnaround),
+        response: of(|m| m.response),
+        cpu_time: of(|m| m.cpu_time),
+        io_time: of(|m| m.io_time),
+        preemptions: of(|m| m.preemptions),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        metrics,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    fn cpu_only(bursts: &[(i32, i32, i32)]) -> Vec<Process> {
+        bursts
+            .iter()
+            .enumerate()
+            .map(|(pid, &(arrival, priority, burst))| {
+                Process::new(format!("P{}", pid + 1), pid as i32, priority, vec![Burst(BurstKind::Cpu, burst)], arrival)
+            })
+            .collect()
+    }
+
+    fn by_pid(processes: Vec<Process>, policy: Policy) -> Vec<ProcessMetrics> {
+        let log = Simulation::with_policy(processes, policy).run();
+        let mut metrics = metrics::process_metrics(&log.content);
+        metrics.sort_by_key(|m| m.pid);
+        metrics
+    }
+
+    #[test]
+    fn test_distribution() {
+        let dist = Distribution::of(&[4., 1., 3., 2.]).unwrap();
+        assert_eq!(dist.min, 1.);
+        assert_eq!(dist.mean, 2.5);
+        assert_eq!(dist.median, 2.5);
+        assert_eq!(dist.p95, 4.);
+        assert_eq!(dist.max, 4.);
+        assert_eq!(Distribution::of(&[]), None);
+    }
+
+    // the P1 = 24, P2 = 3, P3 = 3 example every OS textbook opens with.
+    #[test]
+    fn test_fcfs_textbook() {
+        let metrics = by_pid(cpu_only(&[(0, 0, 24), (0, 0, 3), (0, 0, 3)]), Policy::Fcfs);
+        assert_eq!(metrics.iter().map(|m| m.wait).collect::<Vec<_>>(), vec![0, 24, 27]);
+        assert_eq!(metrics.iter().map(|m| m.turnaround).collect::<Vec<_>>(), vec![24, 27, 30]);
+        assert_eq!(metrics.iter().map(|m| m.response).collect::<Vec<_>>(), vec![0, 24, 27]);
+        let stats = statistics(&metrics);
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.wait.unwrap().mean, 17.);
+        assert_eq!(stats.turnaround.unwrap().mean, 27.);
+        assert_eq!(stats.wait.unwrap().median, 24.);
+    }
+
+    #[test]
+    fn test_round_robin_textbook() {
+        let metrics = by_pid(cpu_only(&[(0, 0, 24), (0, 0, 3), (0, 0, 3)]), Policy::RoundRobin { quantum: 4 });
+        assert_eq!(metrics.iter().map(|m| m.wait).collect::<Vec<_>>(), vec![6, 4, 7]);
+        assert_eq!(metrics.iter().map(|m| m.turnaround).collect::<Vec<_>>(), vec![30, 7, 10]);
+        assert_eq!(metrics.iter().map(|m| m.response).collect::<Vec<_>>(), vec![0, 4, 7]);
+        assert_eq!(metrics.iter().map(|m| m.preemptions).collect::<Vec<_>>(), vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_priority_textbook() {
+        let metrics = by_pid(
+            cpu_only(&[(0, 3, 10), (0, 1, 1), (0, 4, 2), (0, 5, 1), (0, 2, 5)]),
+            Policy::Priority,
+        );
+        assert_eq!(metrics.iter().map(|m| m.wait).collect::<Vec<_>>(), vec![6, 0, 16, 18, 1]);
+        assert_eq!(statistics(&metrics).wait.unwrap().mean, 8.2);
+    }
+
+    #[test]
+    fn test_late_arrival_and_io() {
+        // P1 runs 0-2, does IO 2-5, runs again 5-6. P2 arrives at 1 and runs 2-4.
+        let processes = vec![
+            Process::new("P1".to_string(), 0, 0, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 3), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("P2".to_string(), 1, 0, vec![Burst(BurstKind::Cpu, 2)], 1),
+        ];
+        let metrics = by_pid(processes, Policy::Fcfs);
+        assert_eq!((metrics[0].completion, metrics[0].turnaround, metrics[0].wait), (6, 6, 0));
+        assert_eq!((metrics[1].first_run, metrics[1].response, metrics[1].wait), (2, 1, 1));
+        assert_eq!((metrics[0].cpu_time, metrics[0].io_time), (3, 3));
+    }
+}
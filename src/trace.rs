@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+
+use crate::process::{Burst, BurstKind, Process};
+
+// Importer for workloads recorded from real systems. Both supported formats boil down to
+// a list of "at time T, process P was in state S" observations. The state a process is
+// observed in is assumed to hold until the next observation of that process, which works
+// for transition logs (every change is recorded) and for sampled snapshots alike.
+//
+// Running/runnable time becomes CPU bursts, sleeping/blocked time becomes IO bursts.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TraceState {
+    Running,
+    Waiting,
+    Exited,
+}
+
+#[derive(Debug, Clone)]
+struct Observation {
+    time: f64,
+    pid: i32,
+    name: Option<String>,
+    priority: Option<i32>,
+    state: TraceState,
+}
+
+fn parse_state(state: &str) -> Option<TraceState> {
+    // single letters are the ones used by /proc/<pid>/stat (see proc(5)).
+    match state.trim().to_ascii_lowercase().as_str() {
+        "r" | "running" | "runnable" | "ready" => Some(TraceState::Running),
+        "s" | "d" | "i" | "t" | "w" | "sleeping" | "waiting" | "blocked" | "io" => Some(TraceState::Waiting),
+        "z" | "x" | "exit" | "exited" | "dead" | "zombie" => Some(TraceState::Exited),
+        _ => None,
+    }
+}
+
+/// Parses a CSV of `timestamp,pid,state[,name[,priority]]` rows. A header on the first row,
+/// blank lines and lines starting with `#` are skipped, and an empty name or priority is the
+/// same as leaving it out. `tick` is how many timestamp units make up one simulator tick.
+pub fn from_csv(content: &str, tick: f64) -> Result<Vec<Process>, String> {
+    let mut observations = vec![];
+    let mut first_row = true;
+    for (line_no, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let header_allowed = std::mem::replace(&mut first_row, false);
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let time = match fields[0].parse::<f64>() {
+            Ok(v) => v,
+            // a header row is the only thing allowed to not start with a timestamp.
+            Err(_) if header_allowed => continue,
+            Err(_) => return Err(format!("line {}: bad timestamp {:?}", line_no + 1, fields[0])),
+        };
+        if fields.len() < 3 {
+            return Err(format!("line {}: expected timestamp,pid,state", line_no + 1));
+        }
+        let pid = fields[1]
+            .parse()
+            .map_err(|_| format!("line {}: bad pid {:?}", line_no + 1, fields[1]))?;
+        let state = parse_state(fields[2])
+            .ok_or_else(|| format!("line {}: unknown state {:?}", line_no + 1, fields[2]))?;
+        let name = fields.get(3).filter(|name| !name.is_empty()).map(|name| name.to_string());
+        let priority = match fields.get(4).filter(|v| !v.is_empty()) {
+            Some(v) => Some(v.parse().map_err(|_| format!("line {}: bad priority {:?}", line_no + 1, v))?),
+            None => None,
+        };
+        observations.push(Observation { time, pid, name, priority, state });
+    }
+    build_processes(observations, tick)
+}
+
+/// Parses sampled `/proc/<pid>/stat` snapshots, one per line, each prefixed with the time it
+/// was taken at. Something like this captures them:
+///
+/// `while sleep 0.01; do for f in /proc/[0-9]*/stat; do echo "$(date +%s.%N) $(cat $f)"; done; done`
+pub fn from_proc_stat(content: &str, tick: f64) -> Result<Vec<Process>, String> {
+    let mut observations = vec![];
+    for (line_no, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (time, stat) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("line {}: expected a timestamp followed by a stat line", line_no + 1))?;
+        let time = time
+            .parse()
+            .map_err(|_| format!("line {}: bad timestamp {:?}", line_no + 1, time))?;
+        // the command name is in parens and can itself contain spaces and parens,
+        // so the only reliable way to find its end is the last ')'.
+        let (open, close) = match (stat.find('('), stat.rfind(')')) {
+            (Some(open), Some(close)) if open < close => (open, close),
+            _ => return Err(format!("line {}: no (comm) field", line_no + 1)),
+        };
+        let pid = stat[..open]
+            .trim()
+            .parse()
+            .map_err(|_| format!("line {}: bad pid {:?}", line_no + 1, stat[..open].trim()))?;
+        let name = stat[open + 1..close].to_string();
+        let rest = stat[close + 1..].split_whitespace().collect::<Vec<_>>();
+        let state = rest
+            .first()
+            .and_then(|state| parse_state(state))
+            .ok_or_else(|| format!("line {}: missing or unknown state", line_no + 1))?;
+        // field 18 of the stat line, rest starts at field 3.
+        let priority = rest.get(15).and_then(|priority| priority.parse().ok());
+        observations.push(Observation { time, pid, name: Some(name), priority, state });
+    }
+    build_processes(observations, tick)
+}
+
+fn build_processes(observations: Vec<Observation>, tick: f64) -> Result<Vec<Process>, String> {
+    if tick <= 0. {
+        return Err(format!("tick length must be positive, got {}", tick));
+    }
+    let start = observations.iter().map(|obs| obs.time).fold(f64::INFINITY, f64::min);
+    let end = observations.iter().map(|obs| obs.time).fold(f64::NEG_INFINITY, f64::max);
+    let to_tick = |time: f64| ((time - start) / tick).round() as i32;
+
+    let mut by_pid: BTreeMap<i32, Vec<Observation>> = BTreeMap::new();
+    for obs in observations {
+        by_pid.entry(obs.pid).or_default().push(obs);
+    }
+
+    let mut processes = vec![];
+    for (pid, mut observations) in by_pid {
+        observations.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let name = observations
+            .iter()
+            .rev()
+            .find_map(|obs| obs.name.clone())
+            .unwrap_or_else(|| format!("pid{}", pid));
+        let priority = observations.iter().rev().find_map(|obs| obs.priority).unwrap_or(0);
+
+        // (start tick, kind, length), with neighbouring intervals of the same kind merged.
+        let mut intervals: Vec<(i32, BurstKind, i32)> = vec![];
+        let mut exited = false;
+        for (i, obs) in observations.iter().enumerate() {
+            let kind = match obs.state {
+                TraceState::Running => BurstKind::Cpu,
+                TraceState::Waiting => BurstKind::Io,
+                TraceState::Exited => {
+                    exited = true;
+                    break;
+                }
+            };
+            let until = observations.get(i + 1).map(|next| next.time).unwrap_or(end);
+            let (from, to) = (to_tick(obs.time), to_tick(until));
+            if to <= from {
+                continue;
+            }
+            match intervals.last_mut() {
+                Some((_, last_kind, len)) if *last_kind == kind => *len += to - from,
+                _ => intervals.push((from, kind, to - from)),
+            }
+        }
+
+        // the simulator starts every process on the CPU, so anything it did before first
+        // wanting the CPU is just "not arrived yet".
+        while let Some((_, BurstKind::Io, _)) = intervals.first() {
+            intervals.remove(0);
+        }
+        // a process still asleep when the trace stopped isn't doing IO we know the length of.
+        if !exited {
+            if let Some((_, BurstKind::Io, _)) = intervals.last() {
+                intervals.pop();
+            }
+        }
+        let arrival = match intervals.first() {
+            Some((arrival, _, _)) => *arrival,
+            None => continue,
+        };
+        let bursts = intervals.into_iter().map(|(_, kind, len)| Burst(kind, len)).collect::<Vec<_>>();
+        processes.push(Process::new(name, pid, priority, bursts, arrival));
+    }
+    Ok(processes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_transitions() {
+        let trace = "timestamp,pid,state,name\n\
+                     0,10,R,web\n\
+                     3,10,S\n\
+                     5,10,R\n\
+                     6,10,X\n\
+                     1,11,S,db\n\
+                     2,11,R\n\
+                     6,11,Z\n";
+        let processes = from_csv(trace, 1.).unwrap();
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].name, "web");
+        assert_eq!(processes[0].arrival, 0);
+        assert_eq!(
+            processes[0].burst,
+            vec![Burst(BurstKind::Cpu, 3), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)]
+        );
+        // the leading sleep is dropped and pushes the arrival back.
+        assert_eq!(processes[1].arrival, 2);
+        assert_eq!(processes[1].burst, vec![Burst(BurstKind::Cpu, 4)]);
+    }
+
+    #[test]
+    fn test_csv_tick_scaling_and_merging() {
+        let trace = "0.00,1,R\n0.01,1,R\n0.02,1,D\n0.05,1,R\n0.06,1,S\n0.08,2,R\n";
+        let processes = from_csv(trace, 0.01).unwrap();
+        // samples in the same state merge, the trailing sleep is dropped since it never exits.
+        assert_eq!(
+            processes[0].burst,
+            vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 3), Burst(BurstKind::Cpu, 1)]
+        );
+    }
+
+    #[test]
+    fn test_proc_stat_snapshots() {
+        let trace = "100.0 42 (my (weird) proc) R 1 42 42 0 -1 4194560 0 0 0 0 0 0 0 0 25 5 1\n\
+                     100.5 42 (my (weird) proc) S 1 42 42 0 -1 4194560 0 0 0 0 0 0 0 0 25 5 1\n\
+                     101.0 42 (my (weird) proc) R 1 42 42 0 -1 4194560 0 0 0 0 0 0 0 0 25 5 1\n\
+                     101.5 42 (my (weird) proc) Z 1 42 42 0 -1 4194560 0 0 0 0 0 0 0 0 25 5 1\n";
+        let processes = from_proc_stat(trace, 0.5).unwrap();
+        assert_eq!(processes[0].name, "my (weird) proc");
+        assert_eq!(processes[0].pid, 42);
+        assert_eq!(processes[0].priority, 25);
+        assert_eq!(
+            processes[0].burst,
+            vec![Burst(BurstKind::Cpu, 1), Burst(BurstKind::Io, 1), Burst(BurstKind::Cpu, 1)]
+        );
+    }
+
+    #[test]
+    fn test_bad_state() {
+        assert!(from_csv("0,1,R\n1,1,Q\n", 1.).is_err());
+    }
+
+    #[test]
+    fn test_csv_empty_columns() {
+        // a trailing comma leaves the priority empty, which is the same as no priority.
+        let with_empty = from_csv("0,9,R,x,\n2,9,X,,\n", 1.).unwrap();
+        let without = from_csv("0,9,R,x\n2,9,X\n", 1.).unwrap();
+        assert_eq!(with_empty, without);
+        assert_eq!(with_empty[0].name, "x");
+        assert_eq!(from_csv("0,9,R,x,high\n", 1.).unwrap_err(), "line 1: bad priority \"high\"");
+    }
+
+    #[test]
+    fn test_csv_header() {
+        // only the first row can be a header, comments before it don't count.
+        assert!(from_csv("# captured on host 3\ntimestamp,pid,state\n0,1,R\n1,1,X\n", 1.).is_ok());
+        assert_eq!(
+            from_csv("timestamp,pid,state\ngarbage,1,R\n0,1,R\n", 1.).unwrap_err(),
+            "line 2: bad timestamp \"garbage\""
+        );
+        assert_eq!(from_csv("0,1,R\nlater,1,X\n", 1.).unwrap_err(), "line 2: bad timestamp \"later\"");
+    }
+}
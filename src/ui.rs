@@ -0,0 +1,575 @@
+use std::collections::HashMap;
+
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, List, ListItem, Paragraph, Sparkline, Wrap};
+use tui::{backend::Backend, Frame, Terminal};
+
+use crate::{
+    analysis::{fairness, timeseries},
+    log::{Log, LogEvent, TickEntry},
+    metrics,
+    process::Process,
+    report::gantt,
+    scheduler::SchedulerResult,
+};
+
+pub mod config;
+pub mod inspector;
+pub mod live;
+pub mod log_pane;
+pub mod side_by_side;
+pub mod terminal;
+pub mod view;
+
+use config::{Config, Pane, Theme};
+use view::View;
+
+// Everything that puts a run on screen. Generic over the backend so the same code
+// draws to the terminal in `Log::draw_gui` and to a `TestBackend` in the tests.
+
+const LANE_LABEL_WIDTH: u16 = 5;
+/// Where every pane goes for a given terminal size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Panes {
+    pub status: Rect,
+    pub system_state: Rect,
+    pub cpu_queue: Rect,
+    pub io_queue: Rect,
+    pub finished: Rect,
+    pub future: Rect,
+    pub timeline: Rect,
+    pub process_info: Rect,
+    pub load: Rect,
+    pub log: Rect,
+    pub instructions: Rect,
+}
+
+impl Panes {
+    /// Hidden panes get no room, and are left with an empty `Rect`.
+    pub fn new(area: Rect, config: &Config) -> Self {
+        let shows_any = |panes: &[Pane]| panes.iter().any(|&pane| config.shows(pane));
+        // a row with nothing left in it gives its room to the others.
+        let rows = [
+            shows_any(&Pane::TOP_ROW),
+            shows_any(&[Pane::Timeline, Pane::ProcessInfo, Pane::Load]),
+            shows_any(&[Pane::Log, Pane::Instructions]),
+        ];
+        let heights = rows.iter().zip(config.rows).map(|(&shown, height)| if shown { height } else { 0 }).collect::<Vec<_>>();
+        let total = heights.iter().sum::<u32>().max(1);
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(heights.iter().map(|&height| Constraint::Ratio(height, total)).collect::<Vec<_>>())
+            .split(area);
+        let widths = Pane::TOP_ROW
+            .iter()
+            .map(|&pane| if config.shows(pane) { config.width(pane) } else { 0 })
+            .collect::<Vec<_>>();
+        let total = widths.iter().sum::<u32>().max(1);
+        let first_row = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(widths.iter().map(|&width| Constraint::Ratio(width, total)).collect::<Vec<_>>())
+            .split(main_layout[0]);
+        // the timeline only ever needs a row for the axis and one per lane, the rest goes to the process info.
+        let middle_rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(if config.shows(Pane::Timeline) { config.timeline_height } else { 0 }),
+                Constraint::Min(0),
+            ])
+            .split(main_layout[1]);
+        let process_info_percent = match (config.shows(Pane::ProcessInfo), config.shows(Pane::Load)) {
+            (true, true) => config.process_info_percent as u32,
+            (true, false) => 100,
+            (false, _) => 0,
+        };
+        let middle_row = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Ratio(process_info_percent, 100),
+                Constraint::Ratio(100 - process_info_percent, 100),
+            ])
+            .split(middle_rows[1]);
+        let last_row = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(match (config.shows(Pane::Log), config.shows(Pane::Instructions)) {
+                (true, true) => [Constraint::Min(2), Constraint::Length(config.instructions_height)],
+                (true, false) => [Constraint::Min(0), Constraint::Length(0)],
+                (false, _) => [Constraint::Length(0), Constraint::Min(0)],
+            })
+            .split(main_layout[2]);
+        let only_if = |pane: Pane, area: Rect| if config.shows(pane) { area } else { Rect::default() };
+        Self {
+            status: only_if(Pane::Status, first_row[0]),
+            system_state: only_if(Pane::SystemState, first_row[1]),
+            cpu_queue: only_if(Pane::CpuQueue, first_row[2]),
+            io_queue: only_if(Pane::IoQueue, first_row[3]),
+            finished: only_if(Pane::Finished, first_row[4]),
+            future: only_if(Pane::Future, first_row[5]),
+            timeline: only_if(Pane::Timeline, middle_rows[0]),
+            process_info: only_if(Pane::ProcessInfo, middle_row[0]),
+            load: only_if(Pane::Load, middle_row[1]),
+            log: only_if(Pane::Log, last_row[0]),
+            instructions: only_if(Pane::Instructions, last_row[1]),
+        }
+    }
+}
+
+/// The theme, and the color each process is drawn in everywhere it shows up.
+pub struct Styles {
+    pub theme: Theme,
+    colors: HashMap<i32, Color>,
+}
+
+impl Styles {
+    /// Processes with a color in `config` get that, the rest go round the theme's palette in pid order.
+    pub fn new(config: &Config, content: &[TickEntry]) -> Self {
+        let theme = config.theme();
+        let mut processes = metrics::processes(content);
+        processes.sort_by_key(|proc| proc.pid);
+        let colors = processes
+            .iter()
+            .enumerate()
+            .map(|(i, proc)| {
+                let color = match config.colors.get(&proc.name) {
+                    Some(color) => color.0,
+                    None => theme.palette[i % theme.palette.len()],
+                };
+                (proc.pid, color)
+            })
+            .collect();
+        Self { theme, colors }
+    }
+
+    pub fn color(&self, pid: i32) -> Option<Color> {
+        self.colors.get(&pid).copied()
+    }
+
+    /// What a process's name, or anything else standing for it, is drawn in.
+    pub fn process(&self, pid: i32) -> Style {
+        match self.color(pid) {
+            Some(color) => self.theme.process.fg(color),
+            None => self.theme.process,
+        }
+    }
+
+    pub fn block<'a>(&self, title: impl Into<String>) -> Block<'a> {
+        self.theme.block(title)
+    }
+}
+
+pub fn draw_frame<B: Backend>(term: &mut Terminal<B>, log: &Log, events: &[LogEvent], view: &View, config: &Config) {
+    term.draw(|f| draw(f, log, events, view, config)).unwrap();
+}
+
+/// Draws the run as of the end of tick `view.time - 1`, i.e. the first `view.time` ticks.
+/// `events` is the log of at least that much of the run, worked out ahead of time since
+/// redoing it every frame gets slow on a long run.
+pub fn draw<B: Backend>(f: &mut Frame<B>, log: &Log, events: &[LogEvent], view: &View, config: &Config) {
+    let content = &log.content[0..view.time];
+    let panes = Panes::new(f.size(), config);
+    let styles = Styles::new(config, &log.content);
+    let last = content.last().unwrap();
+    f.render_widget(Block::default().style(styles.theme.background), f.size());
+
+    draw_status(f, panes.status, last, &styles);
+
+    let summary = metrics::summary(content);
+    let fairness = fairness::fairness(content, log.starvation_threshold);
+    f.render_widget(
+        List::new([
+            ListItem::new(format!("TIME: {}", summary.time)),
+            ListItem::new(format!("CPU USAGE: {:.2}", summary.cpu_usage)),
+            ListItem::new(format!("IO USAGE: {:.2}", summary.io_usage)),
+            ListItem::new(format!("AVG WAIT: {:.2}", summary.avg_wait)),
+            ListItem::new(format!("AVG TURNARND: {:.2}", summary.avg_turnaround)),
+            ListItem::new(format!("AVG RESPONSE: {:.2}", summary.avg_response)),
+            ListItem::new(format!("THROUGHPUT: {:.2}", summary.throughput)),
+            ListItem::new(format!("FAIRNESS: {:.2}", fairness.jain_index)),
+            ListItem::new(format!("STARVED: {}", fairness.starved)),
+        ])
+        .block(styles.block("SYSTEM STATE")),
+        panes.system_state,
+    );
+
+    for (title, processes, area) in [
+        ("CPU QUEUE", &last.cpu_queue, panes.cpu_queue),
+        ("IO QUEUE", &last.io_queue, panes.io_queue),
+        ("FINISHED PROCESSES", &last.finished_processes, panes.finished),
+        ("FUTURE PROCESSES", &last.yet_to_arrive, panes.future),
+    ] {
+        draw_processes(f, area, title, processes, &styles);
+    }
+
+    inspector::draw(f, panes.process_info, content, &fairness, view, &styles);
+    draw_timeline(f, panes.timeline, content, view, &styles);
+    draw_load(f, panes.load, content, log.window, &styles);
+    log_pane::draw(f, panes.log, events, view, &styles);
+    let title = match (&view.input, &view.message, view.playing) {
+        (Some(input), _, _) => input.prompt(),
+        (None, Some(message), _) => message.clone(),
+        (None, None, true) => format!("Instructions - PLAYING at {} ticks/s", view.ticks_per_second()),
+        (None, None, false) => format!("Instructions - PAUSED, plays at {} ticks/s", view.ticks_per_second()),
+    };
+    // the engine has simulated every tick in the log, so the next one is the log's length.
+    let title = if view.live { format!("LIVE: edits apply at T{} - {}", log.content.len(), title) } else { title };
+    let live_keys = if view.live {
+        "n adds a process, r changes the selected process's priority, x kills it, right at the end simulates a tick. \
+         w throws away everything after the cursor so edits apply from there, m switches scheduler. "
+    } else {
+        ""
+    };
+    f.render_widget(
+        Paragraph::new(format!(
+            "{}Left / right step back / forward in time, PgUp / PgDn and Home / End jump. Space plays / pauses, \
+             f and s play faster / slower, g goes to a tick. a, c, p and i jump to the next arrival, completion, \
+             preemption or idle CPU (shift for the previous one). + and - zoom the timeline, [ and ] scroll it. \
+             Up / down pick a process, d shows its details. Press q to exit.",
+            live_keys
+        ))
+        .wrap(Wrap { trim: true })
+        .block(styles.block(title)),
+        panes.instructions,
+    );
+}
+
+// what the CPU and IO are doing in `entry`.
+fn draw_status<B: Backend>(f: &mut Frame<B>, area: Rect, entry: &TickEntry, styles: &Styles) {
+    let line = |device: &str, result: &SchedulerResult| match result {
+        SchedulerResult::Finished(p) => Spans::from(vec![
+            Span::raw(format!("{}: FINISHED ", device)),
+            Span::styled(p.name.clone(), styles.process(p.pid)),
+        ]),
+        SchedulerResult::Processing(p) => Spans::from(vec![
+            Span::raw(format!("{}: PROCESSING ", device)),
+            Span::styled(p.name.clone(), styles.process(p.pid)),
+        ]),
+        SchedulerResult::Idle | SchedulerResult::NoBurstLeft => Spans::from(format!("{}: IDLE", device)),
+        // the engine gives up on the run before it gets here, but there's no reason to take the terminal down with it.
+        SchedulerResult::WrongKind => Spans::from(format!("{}: ERR", device)),
+    };
+    f.render_widget(
+        List::new([ListItem::new(line("CPU0", &entry.cpu_process)), ListItem::new(line("IO0", &entry.io_process))])
+            .block(styles.block("STATUS")),
+        area,
+    );
+}
+
+// a list of process names, e.g. one of the queues.
+fn draw_processes<B: Backend>(f: &mut Frame<B>, area: Rect, title: &str, processes: &[Process], styles: &Styles) {
+    f.render_widget(
+        List::new(
+            processes
+                .iter()
+                .map(|process| ListItem::new(Span::styled(process.name.clone(), styles.process(process.pid))))
+                .collect::<Vec<_>>(),
+        )
+        .block(styles.block(title)),
+        area,
+    );
+}
+
+// which of a tick's devices to look at.
+type Device = fn(&TickEntry) -> &SchedulerResult;
+
+// the pid that had the device for most of `ticks`, ties going to the earliest.
+fn busiest(ticks: &[TickEntry], device: Device) -> Option<i32> {
+    let mut counts: Vec<(i32, usize)> = vec![];
+    for entry in ticks {
+        if let SchedulerResult::Finished(p) | SchedulerResult::Processing(p) = device(entry) {
+            match counts.iter_mut().find(|(pid, _)| *pid == p.pid) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((p.pid, 1)),
+            }
+        }
+    }
+    counts.iter().rev().max_by_key(|(_, count)| *count).map(|(pid, _)| *pid)
+}
+
+// a Gantt chart of everything up to the cursor, each cell showing whoever had the
+// device most during its ticks, scrolled so the cursor stays in view unless panned away.
+fn draw_timeline<B: Backend>(f: &mut Frame<B>, area: Rect, content: &[TickEntry], view: &View, styles: &Styles) {
+    let per_cell = view.ticks_per_cell();
+    let block = styles.block(format!("TIMELINE ({} tick{} per cell)", per_cell, if per_cell == 1 { "" } else { "s" }));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let width = inner.width.saturating_sub(LANE_LABEL_WIDTH) as usize;
+    let cells = content.len().div_ceil(per_cell);
+    let cursor = (content.len() - 1) / per_cell;
+    let follow = (cursor + 1).saturating_sub(width);
+    let first = (follow as isize + view.pan).clamp(0, cells.saturating_sub(1) as isize) as usize;
+    let shown = first..(first + width).min(cells);
+
+    let mut axis = vec![' '; width + 8];
+    for cell in shown.clone().filter(|cell| cell % 10 == 0) {
+        for (i, digit) in (cell * per_cell).to_string().chars().enumerate() {
+            axis[cell - first + i] = digit;
+        }
+    }
+    let mut lines = vec![Spans::from(format!(
+        "{:width$}{}",
+        "",
+        axis[..width].iter().collect::<String>(),
+        width = LANE_LABEL_WIDTH as usize
+    ))];
+
+    let symbols = gantt::symbols(content);
+    let lanes: [(&str, Device); 2] = [("CPU0", |entry| &entry.cpu_process), ("IO0", |entry| &entry.io_process)];
+    for (label, device) in lanes {
+        let mut spans = vec![Span::raw(format!("{:width$}", label, width = LANE_LABEL_WIDTH as usize))];
+        for cell in shown.clone() {
+            let ticks = &content[cell * per_cell..((cell + 1) * per_cell).min(content.len())];
+            let (symbol, mut style) = match busiest(ticks, device) {
+                Some(pid) => (symbols[&pid], styles.process(pid)),
+                None => (gantt::IDLE, Style::default()),
+            };
+            if cell == cursor {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(symbol.to_string(), style));
+        }
+        lines.push(Spans::from(spans));
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+// sparklines of the most recent samples, as many as fit across the pane.
+fn draw_load<B: Backend>(f: &mut Frame<B>, area: Rect, content: &[TickEntry], window: usize, styles: &Styles) {
+    let block = styles.block(format!("LOAD (CPU/IO over {} ticks, queues)", window));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let samples = timeseries::time_series(content, window);
+    let shown = &samples[samples.len().saturating_sub(inner.width as usize)..];
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+        ])
+        .split(inner);
+    let max_queue = samples.iter().map(|s| s.ready_queue.max(s.io_queue)).max().unwrap_or(0).max(1) as u64;
+    let last = samples.last().unwrap();
+    let lines: [(String, Vec<u64>, u64); 4] = [
+        (format!("CPU {:.0}%", last.cpu_utilization * 100.), shown.iter().map(|s| (s.cpu_utilization * 100.) as u64).collect(), 100),
+        (format!("IO {:.0}%", last.io_utilization * 100.), shown.iter().map(|s| (s.io_utilization * 100.) as u64).collect(), 100),
+        (format!("READY {}", last.ready_queue), shown.iter().map(|s| s.ready_queue as u64).collect(), max_queue),
+        (format!("IO QUEUE {}", last.io_queue), shown.iter().map(|s| s.io_queue as u64).collect(), max_queue),
+    ];
+    for ((title, data, max), row) in lines.into_iter().zip(rows.iter()) {
+        f.render_widget(
+            Sparkline::default()
+                .block(Block::default().title(Span::styled(title, styles.theme.title)))
+                .data(&data)
+                .max(max),
+            *row,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::{backend::TestBackend, buffer::Buffer, widgets::Borders};
+
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    const WIDTH: u16 = 120;
+    const HEIGHT: u16 = 36;
+
+    // A runs 0-2 then does IO 2-4, B arrives at 1 and runs 2-5, C arrives at 6.
+    fn log() -> Log {
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 3)], 1),
+            Process::new("C".to_string(), 2, 3, vec![Burst(BurstKind::Cpu, 1)], 6),
+        ];
+        Simulation::with_policy(processes, Policy::Fcfs).run()
+    }
+
+    // the text in `area` of a drawn buffer, a line per row with trailing spaces trimmed.
+    pub fn text(buffer: &Buffer, area: Rect) -> Vec<String> {
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buffer.get(x, y).symbol.as_str())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn render(log: &Log, time: usize) -> (Buffer, Panes) {
+        render_view(log, &View { time, ..View::new() })
+    }
+
+    fn render_view(log: &Log, view: &View) -> (Buffer, Panes) {
+        let mut term = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+        draw_frame(&mut term, log, log.events().events(), view, &Config::default());
+        (term.backend().buffer().clone(), Panes::new(Rect::new(0, 0, WIDTH, HEIGHT), &Config::default()))
+    }
+
+    #[test]
+    fn test_status_and_queues() {
+        let log = log();
+        // end of tick 3: B on the CPU, A just done with IO.
+        let (buffer, panes) = render(&log, 4);
+        assert_eq!(
+            text(&buffer, panes.status)[..4],
+            [
+                "┌STATUS────────────┐",
+                "│CPU0: PROCESSING B│",
+                "│IO0: FINISHED A   │",
+                "│                  │",
+            ]
+        );
+        // A is already back in the ready queue behind B.
+        assert_eq!(text(&buffer, panes.cpu_queue)[..3], ["┌CPU QUEUE─────────┐", "│B                 │", "│A                 │"]);
+        assert_eq!(text(&buffer, panes.io_queue)[..2], ["┌IO QUEUE──────────┐", "│                  │"]);
+        assert_eq!(text(&buffer, panes.future)[..3], ["┌FUTURE PROCESSES──┐", "│C                 │", "│                  │"]);
+    }
+
+    #[test]
+    fn test_system_state() {
+        let log = log();
+        let (buffer, panes) = render(&log, log.content.len());
+        assert_eq!(
+            text(&buffer, panes.system_state)[..10],
+            [
+                "┌SYSTEM STATE──────┐",
+                "│TIME: 6           │",
+                "│CPU USAGE: 1.00   │",
+                "│IO USAGE: 0.29    │",
+                "│AVG WAIT: 0.67    │",
+                "│AVG TURNARND: 3.67│",
+                "│AVG RESPONSE: 0.33│",
+                "│THROUGHPUT: 0.43  │",
+                "│FAIRNESS: 0.93    │",
+                "│STARVED: 0        │",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_log() {
+        let log = log();
+        let (buffer, panes) = render(&log, 3);
+        // newest first.
+        assert_eq!(
+            text(&buffer, panes.log)[..6],
+            [
+                "┌LOG (7 of 7) - / search, o process, e type, j / k scroll──────────────────────────────────────────────────────────────┐",
+                "│T2: NEW PROCESS IS USING IO: A                                                                                        │",
+                "│T2: NEW PROCESS IS USING CPU: B                                                                                       │",
+                "│T1: PROCESSES ARRIVED IN IO QUEUE: [A]                                                                                │",
+                "│T1: PROCESSES ARRIVED IN READY QUEUE: [B]                                                                             │",
+                "│T0: PROCESSES ARRIVED IN READY QUEUE: [A]                                                                             │",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_timeline() {
+        let log = log();
+        let (buffer, panes) = render(&log, 5);
+        let inner = Block::default().borders(Borders::all()).inner(panes.timeline);
+        assert_eq!(text(&buffer, inner)[..3], ["     0", "CPU0 00111", "IO0  ..00."]);
+        // the cursor is the last tick shown.
+        assert!(buffer.get(inner.x + 5 + 4, inner.y + 1).modifier.contains(Modifier::REVERSED));
+        assert!(!buffer.get(inner.x + 5 + 3, inner.y + 1).modifier.contains(Modifier::REVERSED));
+
+        // two ticks a cell over the whole run: CPU is AA BB BA C, IO is .. AA .. .
+        let (buffer, panes) = render_view(&log, &View { time: log.content.len(), zoom: 1, ..View::new() });
+        assert!(text(&buffer, panes.timeline)[0].starts_with("┌TIMELINE (2 ticks per cell)─"));
+        let inner = Block::default().borders(Borders::all()).inner(panes.timeline);
+        assert_eq!(text(&buffer, inner)[..3], ["     0", "CPU0 0112", "IO0  .0.."]);
+    }
+
+    #[test]
+    fn test_process_info() {
+        let log = log();
+        let (buffer, panes) = render(&log, 4);
+        let inner = Block::default().borders(Borders::all()).inner(panes.process_info);
+        assert_eq!(
+            text(&buffer, inner)[..4],
+            [
+                "NAME       PID  PRIO  STATE       WAIT  TURNARND  MAX WAIT NORM TAT REMAINING",
+                "A          0    1     IO          0     -         0        -        CPU 1",
+                "B          1    2     RUNNING     1     -         1        -        CPU 1",
+                "C          2    3     NOT ARRIVED -     -         -        -        CPU 1",
+            ]
+        );
+        let (buffer, panes) = render(&log, log.content.len());
+        let inner = Block::default().borders(Borders::all()).inner(panes.process_info);
+        assert_eq!(text(&buffer, inner)[1], "A          0    1     DONE        1     6         1        1.20     -");
+        assert!(buffer.get(inner.x, inner.y + 1).modifier.contains(Modifier::REVERSED));
+
+        let (buffer, panes) = render_view(&log, &View { time: 4, selected: 1, detail: true, ..View::new() });
+        let inner = Block::default().borders(Borders::all()).inner(panes.process_info);
+        assert_eq!(
+            text(&buffer, inner)[..4],
+            [
+                "priority 2, arrives T1, RUNNING, remaining: CPU 1",
+                "T0   -##",
+                "    # running, - ready, = IO, ~ waiting for IO",
+                "T2-T4       CPU   2 ticks, still going",
+            ]
+        );
+
+        let empty = Simulation::with_policy(vec![], Policy::Fcfs).run();
+        let (buffer, panes) = render_view(&empty, &View { detail: true, ..View::new() });
+        let inner = Block::default().borders(Borders::all()).inner(panes.process_info);
+        assert_eq!(text(&buffer, inner)[0], "no processes");
+    }
+
+    #[test]
+    fn test_small_terminal() {
+        // a resize can leave the viewer almost no room, which shouldn't take it down.
+        let log = log();
+        let events = log.events();
+        for (width, height) in [(1, 1), (10, 4), (30, 12), (60, 20)] {
+            let mut term = Terminal::new(TestBackend::new(width, height)).unwrap();
+            for time in 1..=log.content.len() {
+                let view = View { time, detail: time % 2 == 0, zoom: time % view::ZOOM_LEVELS.len(), ..View::new() };
+                draw_frame(&mut term, &log, events.events(), &view, &Config::default());
+            }
+        }
+    }
+
+    #[test]
+    fn test_config() {
+        let log = log();
+        let config = Config::parse(
+            r#"{"theme": "high-contrast", "colors": {"B": "white"}, "hide": ["future", "load"], "widths": {"cpu-queue": 2}}"#,
+        )
+        .unwrap();
+        let panes = Panes::new(Rect::new(0, 0, WIDTH, HEIGHT), &config);
+        assert_eq!((panes.future, panes.load), (Rect::default(), Rect::default()));
+        // five panes left on the top row, the CPU queue getting two shares of six.
+        assert_eq!((panes.status.width, panes.cpu_queue.width), (20, 40));
+        assert_eq!(panes.process_info.width, WIDTH);
+
+        let mut term = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+        draw_frame(&mut term, &log, log.events().events(), &View { time: 4, ..View::new() }, &config);
+        let buffer = term.backend().buffer();
+        // B is white everywhere it shows up, A takes the palette's first color.
+        let queue = Block::default().borders(Borders::all()).inner(panes.cpu_queue);
+        assert_eq!(buffer.get(queue.x, queue.y).symbol, "B");
+        assert_eq!(buffer.get(queue.x, queue.y).fg, Color::White);
+        assert_eq!(buffer.get(queue.x, queue.y + 1).fg, Theme::high_contrast().palette[0]);
+        assert!(buffer.get(queue.x, queue.y).modifier.contains(Modifier::BOLD));
+        let status = Block::default().borders(Borders::all()).inner(panes.status);
+        assert_eq!(text(buffer, status)[0], "CPU0: PROCESSING B");
+        assert_eq!(buffer.get(status.x + 17, status.y).fg, Color::White);
+        assert_eq!(buffer.get(0, 0).bg, Color::Black);
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::{Block, Borders};
+
+// How the viewer looks, read from the JSON file given with `--config`. Everything is
+// optional, e.g.
+//
+//     {
+//         "theme": "high-contrast",
+//         "colors": { "editor": "light-green", "backup": "#ff8800" },
+//         "hide": ["future", "load"],
+//         "widths": { "cpu-queue": 2, "io-queue": 2 },
+//         "rows": [1, 1, 1]
+//     }
+
+/// A pane of the main viewer, as named in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pane {
+    Status,
+    SystemState,
+    CpuQueue,
+    IoQueue,
+    Finished,
+    Future,
+    Timeline,
+    ProcessInfo,
+    Load,
+    Log,
+    Instructions,
+}
+
+impl Pane {
+    /// The panes along the top row, left to right.
+    pub const TOP_ROW: [Pane; 6] = [
+        Pane::Status,
+        Pane::SystemState,
+        Pane::CpuQueue,
+        Pane::IoQueue,
+        Pane::Finished,
+        Pane::Future,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    HighContrast,
+}
+
+/// A color as written in the config: a name like `red` or `light-blue`, or `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ConfigColor(pub Color);
+
+impl TryFrom<String> for ConfigColor {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, String> {
+        let color = match name.to_ascii_lowercase().as_str() {
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "gray" => Color::Gray,
+            "dark-gray" => Color::DarkGray,
+            "light-red" => Color::LightRed,
+            "light-green" => Color::LightGreen,
+            "light-yellow" => Color::LightYellow,
+            "light-blue" => Color::LightBlue,
+            "light-magenta" => Color::LightMagenta,
+            "light-cyan" => Color::LightCyan,
+            "white" => Color::White,
+            hex if hex.len() == 7 && hex.starts_with('#') => {
+                let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16);
+                match (channel(1), channel(3), channel(5)) {
+                    (Ok(r), Ok(g), Ok(b)) => Color::Rgb(r, g, b),
+                    _ => return Err(format!("bad color {:?}", name)),
+                }
+            }
+            _ => return Err(format!("unknown color {:?}", name)),
+        };
+        Ok(Self(color))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub theme: ThemeName,
+    // by process name, anything not in here gets the next color of the theme's palette.
+    pub colors: HashMap<String, ConfigColor>,
+    pub hide: Vec<Pane>,
+    // relative widths of the top row's panes, 1 for any not in here.
+    pub widths: HashMap<Pane, u32>,
+    // relative heights of the top, middle and bottom rows.
+    pub rows: [u32; 3],
+    pub timeline_height: u16,
+    pub instructions_height: u16,
+    // how much of the middle row the process info gets, the load gets the rest.
+    pub process_info_percent: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: ThemeName::Default,
+            colors: HashMap::new(),
+            hide: vec![],
+            widths: HashMap::new(),
+            rows: [1, 1, 1],
+            // the timeline needs a row for the axis and one per lane.
+            timeline_height: 5,
+            instructions_height: 4,
+            process_info_percent: 67,
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if config.rows.iter().all(|&row| row == 0) {
+            return Err("rows can't all be 0".to_string());
+        }
+        if config.process_info_percent > 100 {
+            return Err(format!("process_info_percent is a percentage, got {}", config.process_info_percent));
+        }
+        Ok(config)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::parse(&json)
+    }
+
+    pub fn shows(&self, pane: Pane) -> bool {
+        !self.hide.contains(&pane)
+    }
+
+    pub fn width(&self, pane: Pane) -> u32 {
+        self.widths.get(&pane).copied().unwrap_or(1)
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self.theme {
+            ThemeName::Default => Theme::default(),
+            ThemeName::HighContrast => Theme::high_contrast(),
+        }
+    }
+}
+
+/// The styles everything is drawn with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    // cycled through by pid for processes without a color of their own.
+    pub palette: Vec<Color>,
+    // added to a process's color wherever it's drawn.
+    pub process: Style,
+    // under everything, borders and titles go on top.
+    pub background: Style,
+    pub border: Style,
+    pub title: Style,
+    // the selected row and the timeline cursor.
+    pub highlight: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            palette: vec![
+                Color::Red,
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::LightRed,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightBlue,
+                Color::LightMagenta,
+                Color::LightCyan,
+            ],
+            process: Style::default(),
+            background: Style::default(),
+            border: Style::default(),
+            title: Style::default(),
+            highlight: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+}
+
+impl Theme {
+    /// Bold, bright and on black, for washed out screens and projectors.
+    pub fn high_contrast() -> Self {
+        Self {
+            palette: vec![
+                Color::LightYellow,
+                Color::LightCyan,
+                Color::LightGreen,
+                Color::LightMagenta,
+                Color::LightRed,
+                Color::White,
+            ],
+            process: Style::default().add_modifier(Modifier::BOLD),
+            background: Style::default().fg(Color::White).bg(Color::Black),
+            border: Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            title: Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+            highlight: Style::default().fg(Color::Black).bg(Color::LightYellow).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// A bordered pane with `title`.
+    pub fn block<'a>(&self, title: impl Into<String>) -> Block<'a> {
+        Block::default()
+            .title(tui::text::Span::styled(title.into(), self.title))
+            .borders(Borders::all())
+            .border_style(self.border)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r##"{"theme": "high-contrast", "colors": {"A": "light-green", "B": "#ff8800"}, "hide": ["future"], "widths": {"cpu-queue": 2}}"##,
+        )
+        .unwrap();
+        assert_eq!(config.theme(), Theme::high_contrast());
+        assert_eq!(config.colors["A"], ConfigColor(Color::LightGreen));
+        assert_eq!(config.colors["B"], ConfigColor(Color::Rgb(0xff, 0x88, 0x00)));
+        assert!(!config.shows(Pane::Future) && config.shows(Pane::Load));
+        assert_eq!((config.width(Pane::CpuQueue), config.width(Pane::IoQueue)), (2, 1));
+        // the rest is left as it was.
+        assert_eq!(config.rows, Config::default().rows);
+
+        assert_eq!(Config::parse("{}").unwrap(), Config::default());
+        assert!(Config::parse(r#"{"colors": {"A": "mauve"}}"#).unwrap_err().contains("unknown color \"mauve\""));
+        assert!(Config::parse(r#"{"hide": ["sidebar"]}"#).is_err());
+        assert!(Config::parse(r#"{"row": [1, 1, 1]}"#).is_err());
+        assert!(Config::parse(r#"{"rows": [0, 0, 0]}"#).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use tui::backend::Backend;
+use tui::layout::{Constraint, Rect};
+use tui::style::{Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Cell, Paragraph, Row, Table, TableState};
+use tui::Frame;
+
+use crate::{
+    analysis::fairness::Fairness,
+    log::TickEntry,
+    metrics,
+    process::{BurstKind, Process},
+    report::{segments, Lane},
+    scheduler::SchedulerResult,
+};
+
+use super::{view::View, Styles};
+
+// The PROCESS INFO pane: a table of every process as of the cursor, or everything
+// that's happened to the selected one so far.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    NotArrived,
+    Ready,
+    Running,
+    // waiting in the IO queue for the device.
+    Blocked,
+    DoingIo,
+    Done,
+    // taken out of a live run.
+    Killed,
+}
+
+impl State {
+    fn label(self) -> &'static str {
+        match self {
+            State::NotArrived => "NOT ARRIVED",
+            State::Ready => "READY",
+            State::Running => "RUNNING",
+            State::Blocked => "IO WAIT",
+            State::DoingIo => "IO",
+            State::Done => "DONE",
+            State::Killed => "KILLED",
+        }
+    }
+
+    // the character the process's own timeline uses for a tick in this state.
+    fn symbol(self) -> char {
+        match self {
+            State::NotArrived | State::Done | State::Killed => ' ',
+            State::Ready => '-',
+            State::Running => '#',
+            State::Blocked => '~',
+            State::DoingIo => '=',
+        }
+    }
+}
+
+fn on(result: &SchedulerResult, pid: i32) -> bool {
+    matches!(result, SchedulerResult::Finished(p) | SchedulerResult::Processing(p) if p.pid == pid)
+}
+
+pub fn state(pid: i32, entry: &TickEntry) -> State {
+    let has = |queue: &[Process]| queue.iter().any(|proc| proc.pid == pid);
+    if on(&entry.cpu_process, pid) {
+        State::Running
+    } else if on(&entry.io_process, pid) {
+        State::DoingIo
+    } else if has(&entry.finished_processes) {
+        State::Done
+    } else if has(&entry.cpu_queue) {
+        State::Ready
+    } else if has(&entry.io_queue) {
+        State::Blocked
+    } else if has(&entry.yet_to_arrive) {
+        State::NotArrived
+    } else {
+        // every process is somewhere in every tick until it's killed.
+        State::Killed
+    }
+}
+
+// the process as it stood at the end of `entry`, with whatever bursts it had left.
+fn current(pid: i32, entry: &TickEntry) -> Option<Process> {
+    metrics::all_processes(entry).into_iter().find(|proc| proc.pid == pid)
+}
+
+fn bursts(proc: &Process) -> String {
+    if proc.burst.is_empty() {
+        return "-".to_string();
+    }
+    proc.burst
+        .iter()
+        .map(|burst| match burst.0 {
+            BurstKind::Cpu => format!("CPU {}", burst.1),
+            BurstKind::Io => format!("IO {}", burst.1),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The processes in table order.
+pub fn processes(content: &[TickEntry]) -> Vec<Process> {
+    let mut processes = metrics::processes(content);
+    processes.sort_by_key(|proc| proc.pid);
+    processes
+}
+
+// ticks in the system so far without being on a device, the same as `metrics::wait_time`
+// but for a process that may not be done yet.
+fn wait_so_far(proc: &Process, content: &[TickEntry]) -> Option<i32> {
+    // a killed process stopped waiting when it was killed.
+    let gone = content.iter().rposition(|entry| state(proc.pid, entry) != State::Killed).map_or(0, |tick| tick + 1);
+    let until = metrics::completion_time(proc.pid, content).unwrap_or(gone as i32);
+    let in_system = until - proc.arrival;
+    (in_system > 0).then(|| in_system - metrics::cpu_time(proc.pid, content) - metrics::io_time(proc.pid, content))
+}
+
+pub fn draw<B: Backend>(f: &mut Frame<B>, area: Rect, content: &[TickEntry], fairness: &Fairness, view: &View, styles: &Styles) {
+    let processes = processes(content);
+    let selected = view.selected.min(processes.len().saturating_sub(1));
+    if view.detail {
+        // an empty workload, or a live run nothing's been added to yet.
+        match processes.get(selected) {
+            Some(proc) => draw_detail(f, area, content, proc, styles),
+            None => f.render_widget(Paragraph::new("no processes").block(styles.block("PROCESS INFO - d or esc to go back")), area),
+        }
+        return;
+    }
+
+    let last = content.last().unwrap();
+    let header = ["NAME", "PID", "PRIO", "STATE", "WAIT", "TURNARND", "MAX WAIT", "NORM TAT", "REMAINING"];
+    let rows = processes.iter().map(|proc| {
+        let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+        // processes that haven't arrived yet aren't in there.
+        let fair = fairness.processes.iter().find(|fair| fair.pid == proc.pid);
+        Row::new(vec![
+            Cell::from(proc.name.clone()).style(styles.process(proc.pid)),
+            Cell::from(proc.pid.to_string()),
+            Cell::from(proc.priority.to_string()),
+            Cell::from(state(proc.pid, last).label()),
+            Cell::from(or_dash(wait_so_far(proc, content).map(|wait| wait.to_string()))),
+            Cell::from(or_dash(metrics::turn_around_time(proc.pid, content).map(|t| t.to_string()))),
+            Cell::from(or_dash(fair.map(|fair| {
+                format!("{}{}", fair.max_continuous_wait, if fair.starved { " !" } else { "" })
+            }))),
+            Cell::from(or_dash(fair.and_then(|fair| fair.normalized_turnaround).map(|t| format!("{:.2}", t)))),
+            Cell::from(current(proc.pid, last).map(|proc| bursts(&proc)).unwrap_or_default()),
+        ])
+    });
+    let table = Table::new(rows)
+        .header(Row::new(header.to_vec()).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(styles.block("PROCESS INFO (up / down select, d for details, ! starved)"))
+        .widths(&[
+            Constraint::Length(10),
+            Constraint::Length(4),
+            Constraint::Length(5),
+            Constraint::Length(11),
+            Constraint::Length(5),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ])
+        .highlight_style(styles.theme.highlight);
+    let mut state = TableState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_detail<B: Backend>(f: &mut Frame<B>, area: Rect, content: &[TickEntry], proc: &Process, styles: &Styles) {
+    let block = styles.block(format!("PROCESS {} (pid {}) - d or esc to go back", proc.name, proc.pid));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let last = content.last().unwrap();
+    let mut lines = vec![Spans::from(format!(
+        "priority {}, arrives T{}, {}, remaining: {}",
+        proc.priority,
+        proc.arrival,
+        state(proc.pid, last).label(),
+        current(proc.pid, last).map(|proc| bursts(&proc)).unwrap_or_default(),
+    ))];
+
+    // one character per tick, the most recent ones if they don't all fit.
+    let states = content.iter().map(|entry| state(proc.pid, entry).symbol()).collect::<String>();
+    let width = inner.width.saturating_sub(4) as usize;
+    let from = states.chars().count().saturating_sub(width);
+    lines.push(Spans::from(vec![
+        Span::raw(format!("T{:<3}", from)),
+        Span::styled(states.chars().skip(from).collect::<String>(), styles.process(proc.pid)),
+    ]));
+    lines.push(Spans::from("    # running, - ready, = IO, ~ waiting for IO"));
+
+    let mut history = segments(content)
+        .into_iter()
+        .filter(|segment| segment.pid == proc.pid)
+        .collect::<Vec<_>>();
+    history.sort_by_key(|segment| segment.start);
+    // the latest ones are the interesting ones when they don't all fit.
+    let room = (inner.height as usize).saturating_sub(lines.len());
+    for segment in history.iter().skip(history.len().saturating_sub(room)) {
+        let device = match segment.lane {
+            Lane::Cpu => "CPU",
+            Lane::Io => "IO ",
+        };
+        let ending = if segment.burst_finished { "burst done" } else if segment.end == content.len() { "still going" } else { "preempted" };
+        lines.push(Spans::from(format!(
+            "{:<12}{} {:>3} ticks, {}",
+            format!("T{}-T{}", segment.start, segment.end),
+            device,
+            segment.end - segment.start,
+            ending
+        )));
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,154 @@
+use crossterm::event::{self, Event, KeyEventKind};
+
+use crate::{
+    engine::Simulation,
+    process::{Burst, BurstKind, Process},
+};
+
+use super::{
+    config::Config,
+    inspector,
+    terminal::TerminalGuard,
+    view::{Action, RunInfo, View},
+};
+
+// The viewer driving the engine instead of replaying a finished log: the run is only
+// simulated as far as it's been watched, so processes can be added, killed or
+// reprioritized partway through and the ticks after that show what it did.
+
+/// Parses a `name priority cpu [io cpu]...` line into a process that arrives at `arrival`.
+pub fn parse_process(line: &str, pid: i32, arrival: i32) -> Result<Process, String> {
+    let mut fields = line.split_whitespace();
+    let name = fields.next().ok_or("expected name priority cpu [io cpu]...")?;
+    let priority = fields.next().ok_or("no priority")?;
+    let priority = priority.parse().map_err(|_| format!("bad priority {:?}", priority))?;
+    let mut next = BurstKind::Cpu;
+    let mut bursts = vec![];
+    for v in fields {
+        let len = v.parse().ok().filter(|&len| len > 0).ok_or_else(|| format!("bad burst {:?}", v))?;
+        bursts.push(Burst(next, len));
+        next = match next {
+            BurstKind::Cpu => BurstKind::Io,
+            BurstKind::Io => BurstKind::Cpu,
+        };
+    }
+    if bursts.is_empty() {
+        return Err("a process needs at least one CPU burst".to_string());
+    }
+    Ok(Process::new(name.to_string(), pid, priority, bursts, arrival))
+}
+
+/// Runs `sim` in the viewer until it's closed, returning it with however much of it was simulated.
+pub fn run(mut sim: Simulation, config: &Config) -> Simulation {
+    let mut guard = TerminalGuard::new().unwrap();
+    let term = &mut guard.term;
+    // the run as it was before each tick, so `history[t]` goes back to having simulated `t` ticks.
+    let mut history = vec![sim.snapshot()];
+    sim.step();
+    let mut view = View { live: true, ..View::new() };
+    let mut run = RunInfo::of(&sim.log.content);
+    // only the new ticks' events are worked out as the run grows.
+    let mut events = sim.log.events();
+    loop {
+        super::draw_frame(term, &sim.log, events.events(), &view, config);
+        let action = if view.playing && !event::poll(view.interval()).unwrap() {
+            if view.time < run.len {
+                view.advance(run.len);
+                continue;
+            }
+            Action::Simulate
+        } else {
+            match event::read().unwrap() {
+                Event::Key(k) if k.kind == KeyEventKind::Press => view.handle_key(k.code, &run),
+                Event::Resize(..) => {
+                    term.autoresize().unwrap();
+                    continue;
+                }
+                _ => continue,
+            }
+        };
+
+        // the process the table has selected, as of the cursor.
+        let selected = || {
+            let processes = inspector::processes(&sim.log.content[..view.time]);
+            processes.get(view.selected.min(processes.len().saturating_sub(1))).cloned()
+        };
+        match action {
+            Action::Continue => {}
+            Action::Quit => break,
+            Action::Simulate if sim.is_done() => {
+                view.playing = false;
+                view.message = Some("Nothing left to simulate, n adds a process".to_string());
+            }
+            Action::Simulate => {
+                history.push(sim.snapshot());
+                sim.step();
+                events.update(&sim.log.content);
+                run = RunInfo::of(&sim.log.content);
+                view.step(1, run.len);
+            }
+            Action::AddProcess(line) => {
+                view.message = Some(match parse_process(&line, sim.next_pid(), sim.time()) {
+                    Ok(proc) => {
+                        let message = format!("{} (pid {}) arrives at T{}", proc.name, proc.pid, proc.arrival);
+                        sim.inject(proc);
+                        message
+                    }
+                    Err(e) => format!("Couldn't add process: {}", e),
+                });
+            }
+            Action::SetPriority(priority) => {
+                view.message = selected().map(|proc| {
+                    if sim.set_priority(proc.pid, priority) {
+                        format!("{} now has priority {}", proc.name, priority)
+                    } else {
+                        format!("{} isn't in the run anymore", proc.name)
+                    }
+                });
+            }
+            Action::Kill => {
+                view.message = selected().map(|proc| match sim.kill(proc.pid) {
+                    Some(_) => format!("Killed {}", proc.name),
+                    None => format!("{} isn't in the run anymore", proc.name),
+                });
+            }
+            Action::Rewind if view.time == run.len => {
+                view.message = Some("Already at the end, move back to rewind".to_string());
+            }
+            Action::Rewind => {
+                sim.restore(&history[view.time]);
+                history.truncate(view.time);
+                // the events of the thrown away ticks can't be taken back out one by one.
+                events = sim.log.events();
+                run = RunInfo::of(&sim.log.content);
+                view.playing = false;
+                view.message = Some(format!("Rewound to T{}", sim.time()));
+            }
+            Action::SetPolicy(policy) => {
+                sim.set_policy(policy);
+                view.message = Some(format!("Switched to {} from T{}", policy, sim.time()));
+            }
+        }
+    }
+    drop(guard);
+    sim
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_process() {
+        let proc = parse_process("D 2 3 4 1", 5, 7).unwrap();
+        assert_eq!((proc.name.as_str(), proc.pid, proc.priority, proc.arrival), ("D", 5, 2, 7));
+        assert_eq!(
+            proc.burst.into_iter().collect::<Vec<_>>(),
+            vec![Burst(BurstKind::Cpu, 3), Burst(BurstKind::Io, 4), Burst(BurstKind::Cpu, 1)]
+        );
+        assert!(parse_process("", 0, 0).is_err());
+        assert!(parse_process("D 2", 0, 0).is_err());
+        assert_eq!(parse_process("D x 3", 0, 0).unwrap_err(), "bad priority \"x\"");
+        assert_eq!(parse_process("D 2 3 0", 0, 0).unwrap_err(), "bad burst \"0\"");
+    }
+}
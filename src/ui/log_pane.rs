@@ -0,0 +1,160 @@
+use tui::backend::Backend;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::text::{Span, Spans};
+use tui::widgets::Paragraph;
+use tui::Frame;
+
+use crate::log::{LogEvent, LogEventKind};
+
+use super::{view::View, Styles};
+
+// The LOG pane: the log up to the cursor, newest first, narrowed down to whatever is
+// being looked for.
+
+/// Which log lines are shown and how far down them the pane is scrolled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogView {
+    pub kind: Option<LogEventKind>,
+    // only lines about the process with this name.
+    pub process: Option<String>,
+    // only lines containing this, ignoring case.
+    pub search: Option<String>,
+    // lines scrolled past, from the newest.
+    pub scroll: usize,
+}
+
+impl LogView {
+    pub fn matches(&self, event: &LogEvent) -> bool {
+        self.kind.is_none_or(|kind| event.kind == kind)
+            && self.process.as_ref().is_none_or(|name| event.names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+            && self.search.as_ref().is_none_or(|search| {
+                event.to_string().to_ascii_lowercase().contains(&search.to_ascii_lowercase())
+            })
+    }
+
+    /// Moves the type filter on to the next kind of event, or back to all of them after the last.
+    pub fn next_kind(&mut self) {
+        let all = LogEventKind::ALL;
+        self.kind = match self.kind {
+            None => Some(all[0]),
+            Some(kind) => all.iter().position(|&k| k == kind).and_then(|i| all.get(i + 1)).copied(),
+        };
+        self.scroll = 0;
+    }
+}
+
+// `line` with every match of `search` picked out in `style`.
+fn highlight(line: String, search: Option<&String>, style: Style) -> Spans<'static> {
+    let search = match search {
+        Some(search) if !search.is_empty() => search.to_ascii_lowercase(),
+        _ => return Spans::from(line),
+    };
+    let lower = line.to_ascii_lowercase();
+    let mut spans = vec![];
+    let mut from = 0;
+    while let Some(at) = lower[from..].find(&search).map(|at| at + from) {
+        spans.push(Span::raw(line[from..at].to_string()));
+        spans.push(Span::styled(line[at..at + search.len()].to_string(), style));
+        from = at + search.len();
+    }
+    spans.push(Span::raw(line[from..].to_string()));
+    Spans::from(spans)
+}
+
+pub fn draw<B: Backend>(f: &mut Frame<B>, area: Rect, events: &[LogEvent], view: &View, styles: &Styles) {
+    let filter = &view.log;
+    let so_far = events.iter().filter(|event| event.tick < view.time).collect::<Vec<_>>();
+    let shown = so_far.iter().rev().filter(|event| filter.matches(event)).collect::<Vec<_>>();
+
+    let mut title = format!("LOG ({} of {})", shown.len(), so_far.len());
+    if let Some(kind) = filter.kind {
+        title.push_str(&format!(" type {}", kind));
+    }
+    if let Some(name) = &filter.process {
+        title.push_str(&format!(" process {}", name));
+    }
+    if let Some(search) = &filter.search {
+        title.push_str(&format!(" matching \"{}\"", search));
+    }
+    title.push_str(" - / search, o process, e type, j / k scroll");
+    let block = styles.block(title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // no scrolling past the point where the last line is at the bottom.
+    let scroll = filter.scroll.min(shown.len().saturating_sub(inner.height as usize));
+    let lines = shown
+        .iter()
+        .skip(scroll)
+        .take(inner.height as usize)
+        .map(|event| highlight(event.to_string(), filter.search.as_ref(), styles.theme.highlight))
+        .collect::<Vec<_>>();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use tui::style::Modifier;
+
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+        ui::view::RunInfo,
+    };
+
+    #[test]
+    fn test_filters() {
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 2), Burst(BurstKind::Io, 2), Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("B".to_string(), 1, 2, vec![Burst(BurstKind::Cpu, 3)], 1),
+        ];
+        let log = Simulation::with_policy(processes, Policy::Fcfs).run();
+        let events = log.events();
+        let run = RunInfo::of(&log.content);
+        let mut view = View { time: run.len, ..View::new() };
+        let shown = |view: &View| {
+            events.events().iter().filter(|event| view.log.matches(event)).map(|event| event.to_string()).collect::<Vec<_>>()
+        };
+
+        view.handle_key(KeyCode::Char('e'), &run);
+        view.handle_key(KeyCode::Char('e'), &run);
+        assert_eq!(view.log.kind, Some(LogEventKind::Io));
+        assert_eq!(shown(&view), ["T0: IO IS NOW IDLE", "T2: NEW PROCESS IS USING IO: A", "T4: IO IS NOW IDLE"]);
+
+        for key in [KeyCode::Char('o'), KeyCode::Char('b'), KeyCode::Enter] {
+            view.handle_key(key, &run);
+        }
+        assert_eq!(shown(&view), Vec::<String>::new());
+        // on through arrivals, finished and starvation back to everything.
+        for _ in 0..4 {
+            view.handle_key(KeyCode::Char('e'), &run);
+        }
+        assert_eq!(view.log.kind, None);
+        assert_eq!(
+            shown(&view),
+            [
+                "T1: PROCESSES ARRIVED IN READY QUEUE: [B]",
+                "T2: NEW PROCESS IS USING CPU: B",
+                "T4: FINISHED B with TURNAROUND 4 and WAIT 1",
+            ]
+        );
+
+        for key in [KeyCode::Char('/'), KeyCode::Char('f'), KeyCode::Char('i'), KeyCode::Char('n'), KeyCode::Enter] {
+            view.handle_key(key, &run);
+        }
+        assert_eq!(shown(&view), ["T4: FINISHED B with TURNAROUND 4 and WAIT 1"]);
+        let spans = highlight(shown(&view)[0].clone(), view.log.search.as_ref(), Style::default().add_modifier(Modifier::REVERSED));
+        assert_eq!(spans.0[1].content, "FIN");
+        assert!(spans.0[1].style.add_modifier.contains(Modifier::REVERSED));
+
+        // an empty search clears it.
+        for key in [KeyCode::Char('/'), KeyCode::Enter] {
+            view.handle_key(key, &run);
+        }
+        assert_eq!(shown(&view).len(), 3);
+    }
+}
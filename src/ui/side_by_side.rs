@@ -0,0 +1,246 @@
+use crossterm::event::{self, Event, KeyEventKind};
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Modifier, Style};
+use tui::widgets::{Block, Paragraph, Row, Table, Wrap};
+use tui::{backend::Backend, Frame};
+
+use crate::{
+    analysis::fairness,
+    log::{Log, TickEntry},
+    metrics,
+    scheduler::Policy,
+};
+
+use super::{
+    config::Config,
+    terminal::TerminalGuard,
+    view::{Action, RunInfo, View},
+    Styles,
+};
+
+// Two policies' runs of the same workload stepped through together, so it's right there
+// on screen which one got ahead and when.
+
+/// A policy and its run of the workload.
+pub struct Side {
+    pub policy: Policy,
+    pub log: Log,
+}
+
+/// A metric's row name, how to get it out of the first ticks of a run, and whether
+/// more of it is better.
+type Metric = (&'static str, fn(&[TickEntry], &Log) -> f64, bool);
+
+const METRICS: [Metric; 8] = [
+    ("FINISHED", |content, _| content.last().unwrap().finished_processes.len() as f64, true),
+    ("CPU USAGE", |content, _| metrics::summary(content).cpu_usage, true),
+    ("IO USAGE", |content, _| metrics::summary(content).io_usage, true),
+    ("AVG WAIT", |content, _| metrics::summary(content).avg_wait, false),
+    ("AVG TURNARND", |content, _| metrics::summary(content).avg_turnaround, false),
+    ("AVG RESPONSE", |content, _| metrics::summary(content).avg_response, false),
+    ("THROUGHPUT", |content, _| metrics::summary(content).throughput, true),
+    ("FAIRNESS", |content, log| fairness::fairness(content, log.starvation_threshold).jain_index, true),
+];
+
+/// The two runs as one for the key handling: as long as the longer one, with the events of both.
+pub fn run_info(sides: &[Side; 2]) -> RunInfo {
+    let infos = sides.each_ref().map(|side| RunInfo::of(&side.log.content));
+    let mut events = infos.iter().flat_map(|info| info.events.iter().copied()).collect::<Vec<_>>();
+    events.sort_by_key(|event| event.tick);
+    events.dedup();
+    RunInfo {
+        len: infos.iter().map(|info| info.len).max().unwrap(),
+        events,
+        processes: infos[0].processes,
+    }
+}
+
+// a run that's already over stays on its last tick.
+fn side_view(side: &Side, view: &View) -> View {
+    View { time: view.time.min(side.log.content.len()), ..view.clone() }
+}
+
+pub fn draw<B: Backend>(f: &mut Frame<B>, sides: &[Side; 2], view: &View, config: &Config) {
+    // the same workload on both sides, so the same colors.
+    let styles = Styles::new(config, &sides[0].log.content);
+    f.render_widget(Block::default().style(styles.theme.background), f.size());
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(METRICS.len() as u16 + 3),
+            Constraint::Length(4),
+        ])
+        .split(f.size());
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Ratio(1, 2),
+            Constraint::Ratio(1, 2),
+        ])
+        .split(rows[0]);
+    for (side, area) in sides.iter().zip(halves.iter()) {
+        draw_side(f, *area, side, &side_view(side, view), &styles);
+    }
+    draw_metrics(f, rows[1], sides, view, &styles);
+
+    let title = match &view.input {
+        Some(input) => input.prompt(),
+        None if view.playing => format!("Instructions - PLAYING at {} ticks/s", view.ticks_per_second()),
+        None => format!("Instructions - PAUSED, plays at {} ticks/s", view.ticks_per_second()),
+    };
+    f.render_widget(
+        Paragraph::new(
+            "Left / right step both runs back / forward in time, PgUp / PgDn and Home / End jump. Space plays / pauses, \
+             f and s play faster / slower, g goes to a tick. a, c, p and i jump to the next arrival, completion, \
+             preemption or idle CPU in either run (shift for the previous one). + and - zoom the timelines, \
+             [ and ] scroll them. Press q to exit.",
+        )
+        .wrap(Wrap { trim: true })
+        .block(styles.block(title)),
+        rows[2],
+    );
+}
+
+fn draw_side<B: Backend>(f: &mut Frame<B>, area: Rect, side: &Side, view: &View, styles: &Styles) {
+    let content = &side.log.content[..view.time];
+    let len = side.log.content.len();
+    let title = if view.time == len {
+        format!("{} - done at T{}", side.policy, len)
+    } else {
+        side.policy.to_string()
+    };
+    let block = styles.block(title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(5),
+        ])
+        .split(inner);
+    // the status line needs room for a process name, the queues mostly don't.
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(2, 9),
+            Constraint::Ratio(2, 9),
+            Constraint::Ratio(2, 9),
+        ])
+        .split(rows[0]);
+    let last = content.last().unwrap();
+    super::draw_status(f, panes[0], last, styles);
+    super::draw_processes(f, panes[1], "CPU QUEUE", &last.cpu_queue, styles);
+    super::draw_processes(f, panes[2], "IO QUEUE", &last.io_queue, styles);
+    super::draw_processes(f, panes[3], "FINISHED", &last.finished_processes, styles);
+    super::draw_timeline(f, rows[1], content, view, styles);
+}
+
+fn draw_metrics<B: Backend>(f: &mut Frame<B>, area: Rect, sides: &[Side; 2], view: &View, styles: &Styles) {
+    let [left, right] = sides;
+    let (left_view, right_view) = (side_view(left, view), side_view(right, view));
+    let left_content = &left.log.content[..left_view.time];
+    let right_content = &right.log.content[..right_view.time];
+    let header = Row::new(vec![
+        "METRIC".to_string(),
+        left.policy.to_string(),
+        right.policy.to_string(),
+        format!("{} - {}", right.policy, left.policy),
+        "AHEAD".to_string(),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = METRICS.iter().map(|(name, metric, more_is_better)| {
+        let (a, b) = (metric(left_content, &left.log), metric(right_content, &right.log));
+        let ahead = if a == b {
+            "-".to_string()
+        } else if (b > a) == *more_is_better {
+            right.policy.to_string()
+        } else {
+            left.policy.to_string()
+        };
+        Row::new(vec![
+            name.to_string(),
+            format!("{:.2}", a),
+            format!("{:.2}", b),
+            format!("{:+.2}", b - a),
+            ahead,
+        ])
+    });
+    f.render_widget(
+        Table::new(rows)
+            .header(header)
+            .block(styles.block(format!("METRICS AT T{}", view.time - 1)))
+            .widths(&[
+                Constraint::Length(14),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(22),
+                Constraint::Min(12),
+            ]),
+        area,
+    );
+}
+
+/// Shows both runs until the viewer is closed.
+pub fn run(sides: [Side; 2], config: &Config) {
+    let mut guard = TerminalGuard::new().unwrap();
+    let term = &mut guard.term;
+    let mut view = View::new();
+    let run = run_info(&sides);
+    loop {
+        term.draw(|f| draw(f, &sides, &view, config)).unwrap();
+        if view.playing && !event::poll(view.interval()).unwrap() {
+            view.advance(run.len);
+            continue;
+        }
+        match event::read().unwrap() {
+            Event::Key(k) if k.kind == KeyEventKind::Press && view.handle_key(k.code, &run) == Action::Quit => break,
+            Event::Resize(..) => term.autoresize().unwrap(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::{backend::TestBackend, widgets::Borders, Terminal};
+
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        ui::tests::text,
+    };
+
+    #[test]
+    fn test_metrics() {
+        // A hogs the CPU under FCFS, round robin gets the short B and C out first.
+        let processes = vec![
+            Process::new("A".to_string(), 0, 1, vec![Burst(BurstKind::Cpu, 6)], 0),
+            Process::new("B".to_string(), 1, 1, vec![Burst(BurstKind::Cpu, 1)], 0),
+            Process::new("C".to_string(), 2, 1, vec![Burst(BurstKind::Cpu, 1)], 0),
+        ];
+        let sides = [Policy::Fcfs, Policy::RoundRobin { quantum: 1 }].map(|policy| Side {
+            policy,
+            log: Simulation::with_policy(processes.clone(), policy).run(),
+        });
+        let run = run_info(&sides);
+        assert_eq!(run.len, 8);
+
+        let mut term = Terminal::new(TestBackend::new(120, 36)).unwrap();
+        term.draw(|f| draw(f, &sides, &View { time: run.len, ..View::new() }, &Config::default())).unwrap();
+        let buffer = term.backend().buffer();
+        let area = Rect::new(0, 36 - 4 - METRICS.len() as u16 - 3, 120, METRICS.len() as u16 + 3);
+        let lines = text(buffer, Block::default().borders(Borders::all()).inner(area));
+        assert_eq!(lines[0], "METRIC         fcfs         rr:1         rr:1 - fcfs            AHEAD");
+        assert_eq!(lines[1], "FINISHED       3.00         3.00         +0.00                  -");
+        assert_eq!(lines[4], "AVG WAIT       4.33         1.67         -2.67                  rr:1");
+        assert!(text(buffer, Rect::new(0, 0, 60, 1))[0].starts_with("┌fcfs - done at T8"));
+
+        let mut term = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        term.draw(|f| draw(f, &sides, &View::new(), &Config::default())).unwrap();
+    }
+}
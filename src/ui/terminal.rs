@@ -0,0 +1,63 @@
+use std::io::{self, Stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+use crossterm::{
+    cursor,
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tui::{backend::CrosstermBackend, Terminal};
+
+// Getting the user's terminal back the way it was, however the viewer ends. Without this
+// a panic partway through leaves it in raw mode and they have to run `reset`.
+
+// whether there's a terminal to put back, so restoring twice (once from the panic hook,
+// once when the guard is dropped while unwinding) only does it once.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static HOOK: Once = Once::new();
+
+fn restore() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        // nothing more to be done if these fail, we're on the way out either way.
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+    }
+}
+
+/// The terminal the viewer draws to, in raw mode on the alternate screen until this is dropped.
+pub struct TerminalGuard {
+    pub term: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl TerminalGuard {
+    pub fn new() -> io::Result<Self> {
+        // the panic message should end up on the normal screen where it can be read, so
+        // put the terminal back before the default hook prints it.
+        HOOK.call_once(|| {
+            let previous = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                restore();
+                previous(info);
+            }));
+        });
+
+        ACTIVE.store(true, Ordering::SeqCst);
+        let term = terminal::enable_raw_mode()
+            .and_then(|_| execute!(io::stdout(), EnterAlternateScreen, cursor::Hide))
+            .and_then(|_| Terminal::new(CrosstermBackend::new(io::stdout())));
+        match term {
+            Ok(term) => Ok(Self { term }),
+            Err(e) => {
+                restore();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
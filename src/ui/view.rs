@@ -0,0 +1,417 @@
+use std::time::Duration;
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    log::TickEntry,
+    metrics,
+    report::{markers, MarkerKind},
+    scheduler::{Policy, SchedulerResult},
+};
+
+use super::log_pane::LogView;
+
+// Where the viewer is in the run and how it's moving through it. Kept apart from the
+// drawing and from the terminal so the key handling can be tested on its own.
+
+// ticks per timeline cell at each zoom level.
+pub const ZOOM_LEVELS: [usize; 6] = [1, 2, 5, 10, 20, 50];
+// how far `[` and `]` scroll the timeline, in cells.
+pub const PAN_STEP: isize = 10;
+// ticks per second autoplay runs at, `f` and `s` move between them.
+pub const SPEEDS: [usize; 7] = [1, 2, 5, 10, 20, 50, 100];
+// PageUp / PageDown move this many timeline cells, so they go further zoomed out.
+pub const PAGE_CELLS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Arrival,
+    Completion,
+    Preemption,
+    // the CPU going idle.
+    Idle,
+}
+
+/// Something worth jumping to, at the tick the cursor should be put on to see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub kind: EventKind,
+    pub tick: usize,
+}
+
+pub fn events(content: &[TickEntry]) -> Vec<Event> {
+    // markers are at tick boundaries, a completion at `t` is only visible in tick `t - 1`
+    // while the rest are visible in tick `t`.
+    let mut events = markers(content)
+        .into_iter()
+        .map(|marker| match marker.kind {
+            MarkerKind::Arrival => Event { kind: EventKind::Arrival, tick: marker.time },
+            MarkerKind::Completion => Event { kind: EventKind::Completion, tick: marker.time - 1 },
+            MarkerKind::Preemption => Event { kind: EventKind::Preemption, tick: marker.time },
+        })
+        .collect::<Vec<_>>();
+    let busy = |entry: &TickEntry| matches!(entry.cpu_process, SchedulerResult::Finished(_) | SchedulerResult::Processing(_));
+    for (tick, entry) in content.iter().enumerate() {
+        if !busy(entry) && (tick == 0 || busy(&content[tick - 1])) {
+            events.push(Event { kind: EventKind::Idle, tick });
+        }
+    }
+    events.sort_by_key(|event| event.tick);
+    events.dedup();
+    events
+}
+
+/// The bits of a run the key handling needs, worked out once up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunInfo {
+    // ticks in the run.
+    pub len: usize,
+    pub events: Vec<Event>,
+    pub processes: usize,
+}
+
+impl RunInfo {
+    pub fn of(content: &[TickEntry]) -> Self {
+        Self {
+            len: content.len(),
+            events: events(content),
+            processes: metrics::processes(content).len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    GoTo,
+    // a `name priority cpu [io cpu]...` line, only in live mode.
+    NewProcess,
+    Priority,
+    // what the CPU is scheduled with from now on, only in live mode.
+    Policy,
+    // narrowing down the LOG pane.
+    Search,
+    Process,
+}
+
+/// Something being typed in at the bottom of the screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub kind: InputKind,
+    pub text: String,
+}
+
+impl Input {
+    /// What goes in the instructions title while it's being typed.
+    pub fn prompt(&self) -> String {
+        let (asking, enter) = match self.kind {
+            InputKind::GoTo => ("GO TO TICK", "enter to go"),
+            InputKind::NewProcess => ("NEW PROCESS (name priority cpu [io cpu]...)", "enter to add"),
+            InputKind::Priority => ("NEW PRIORITY", "enter to set"),
+            InputKind::Policy => ("NEW SCHEDULER (fcfs, priority or rr:N)", "enter to switch"),
+            InputKind::Search => ("SEARCH THE LOG", "enter to search, empty to clear"),
+            InputKind::Process => ("ONLY LOG PROCESS", "enter to filter, empty to clear"),
+        };
+        format!("{}: {}_ ({}, esc to cancel)", asking, self.text, enter)
+    }
+}
+
+/// What a key asks of whoever owns the run, for the things `View` can't do itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    Quit,
+    // step past the end of a live run.
+    Simulate,
+    AddProcess(String),
+    SetPriority(i32),
+    Kill,
+    // throw away every tick after the cursor and carry on from there.
+    Rewind,
+    SetPolicy(Policy),
+}
+
+/// What the viewer is looking at, as opposed to what's in the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct View {
+    // how many ticks are shown, so the cursor is on tick `time - 1`.
+    pub time: usize,
+    // index into `ZOOM_LEVELS`.
+    pub zoom: usize,
+    // timeline cells scrolled away from where the cursor would be in view, negative is
+    // back in time. Moving the cursor puts it back to 0.
+    pub pan: isize,
+    pub playing: bool,
+    // index into `SPEEDS`.
+    pub speed: usize,
+    pub input: Option<Input>,
+    // row of the process table, which is in pid order.
+    pub selected: usize,
+    // whether the process pane shows the selected process instead of the table.
+    pub detail: bool,
+    // whether the run is being simulated as it's watched, so it can go past its end and be changed.
+    pub live: bool,
+    // what happened to the last change in live mode, until the next key.
+    pub message: Option<String>,
+    pub log: LogView,
+}
+
+impl View {
+    pub fn new() -> Self {
+        Self {
+            time: 1,
+            zoom: 0,
+            pan: 0,
+            playing: false,
+            speed: 3,
+            input: None,
+            selected: 0,
+            detail: false,
+            live: false,
+            message: None,
+            log: LogView::default(),
+        }
+    }
+
+    pub fn ticks_per_cell(&self) -> usize {
+        ZOOM_LEVELS[self.zoom]
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = self.zoom.saturating_sub(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom + 1).min(ZOOM_LEVELS.len() - 1);
+    }
+
+    pub fn ticks_per_second(&self) -> usize {
+        SPEEDS[self.speed]
+    }
+
+    /// How long autoplay waits between ticks.
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(1000 / self.ticks_per_second() as u64)
+    }
+
+    /// Puts the cursor on `tick`, kept within the `len` ticks of the run.
+    pub fn seek(&mut self, tick: usize, len: usize) {
+        self.time = (tick + 1).clamp(1, len);
+        self.pan = 0;
+    }
+
+    pub fn step(&mut self, by: isize, len: usize) {
+        self.seek((self.time as isize - 1 + by).max(0) as usize, len);
+    }
+
+    /// One autoplay tick, pausing once it reaches the end. A live run only ends once
+    /// there's nothing left to simulate, which is up to whoever is simulating it.
+    pub fn advance(&mut self, len: usize) {
+        self.step(1, len);
+        if self.time == len && !self.live {
+            self.playing = false;
+        }
+    }
+
+    fn jump(&mut self, kind: EventKind, forward: bool, run: &RunInfo) {
+        let (events, len) = (&run.events, run.len);
+        let cursor = self.time - 1;
+        let mut matching = events.iter().filter(|event| event.kind == kind);
+        let target = if forward {
+            matching.find(|event| event.tick > cursor)
+        } else {
+            matching.rev().find(|event| event.tick < cursor)
+        };
+        if let Some(event) = target {
+            self.seek(event.tick, len);
+        }
+    }
+
+    fn open(&mut self, kind: InputKind) {
+        self.input = Some(Input { kind, text: String::new() });
+    }
+
+    fn handle_input(&mut self, code: KeyCode, len: usize) -> Action {
+        let Some(input) = &mut self.input else {
+            return Action::Continue;
+        };
+        match code {
+            KeyCode::Char(c) if c.is_ascii_digit() || !matches!(input.kind, InputKind::GoTo | InputKind::Priority) => {
+                input.text.push(c)
+            }
+            KeyCode::Char('-') if input.kind == InputKind::Priority && input.text.is_empty() => input.text.push('-'),
+            KeyCode::Backspace => {
+                input.text.pop();
+            }
+            KeyCode::Enter => {
+                let input = self.input.take().unwrap();
+                match input.kind {
+                    InputKind::GoTo => {
+                        if let Ok(tick) = input.text.parse() {
+                            self.seek(tick, len);
+                        }
+                    }
+                    InputKind::NewProcess => return Action::AddProcess(input.text),
+                    InputKind::Priority => {
+                        if let Ok(priority) = input.text.parse() {
+                            return Action::SetPriority(priority);
+                        }
+                    }
+                    InputKind::Policy => match input.text.parse() {
+                        Ok(policy) => return Action::SetPolicy(policy),
+                        Err(e) => self.message = Some(format!("Couldn't switch scheduler: {}", e)),
+                    },
+                    InputKind::Search | InputKind::Process => {
+                        let text = Some(input.text).filter(|text| !text.is_empty());
+                        if input.kind == InputKind::Search {
+                            self.log.search = text;
+                        } else {
+                            self.log.process = text;
+                        }
+                        self.log.scroll = 0;
+                    }
+                }
+            }
+            KeyCode::Esc => self.input = None,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode, run: &RunInfo) -> Action {
+        let len = run.len;
+        self.message = None;
+        if self.input.is_some() {
+            return self.handle_input(code, len);
+        }
+
+        let page = (PAGE_CELLS * self.ticks_per_cell()) as isize;
+        match code {
+            KeyCode::Left => self.step(-1, len),
+            KeyCode::Right | KeyCode::Enter if self.live && self.time == len => return Action::Simulate,
+            KeyCode::Right | KeyCode::Enter => self.step(1, len),
+            KeyCode::PageUp => self.step(-page, len),
+            KeyCode::PageDown => self.step(page, len),
+            KeyCode::Home => self.seek(0, len),
+            KeyCode::End => self.seek(len, len),
+            KeyCode::Char(' ') => self.playing = !self.playing && (self.time < len || self.live),
+            KeyCode::Char('f') => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+            KeyCode::Char('s') => self.speed = self.speed.saturating_sub(1),
+            KeyCode::Char('g') => self.open(InputKind::GoTo),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom_in(),
+            KeyCode::Char('-') => self.zoom_out(),
+            KeyCode::Char('[') => self.pan -= PAN_STEP,
+            KeyCode::Char(']') => self.pan += PAN_STEP,
+            // lowercase jumps to the next one, uppercase to the previous.
+            KeyCode::Char(c @ ('a' | 'c' | 'p' | 'i' | 'A' | 'C' | 'P' | 'I')) => {
+                let kind = match c.to_ascii_lowercase() {
+                    'a' => EventKind::Arrival,
+                    'c' => EventKind::Completion,
+                    'p' => EventKind::Preemption,
+                    _ => EventKind::Idle,
+                };
+                self.jump(kind, c.is_ascii_lowercase(), run);
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(run.processes.saturating_sub(1)),
+            KeyCode::Char('d') => self.detail = !self.detail,
+            KeyCode::Esc => self.detail = false,
+            KeyCode::Char('/') => self.open(InputKind::Search),
+            KeyCode::Char('o') => self.open(InputKind::Process),
+            KeyCode::Char('e') => self.log.next_kind(),
+            KeyCode::Char('j') => self.log.scroll += 1,
+            KeyCode::Char('k') => self.log.scroll = self.log.scroll.saturating_sub(1),
+            KeyCode::Char('n') if self.live => self.open(InputKind::NewProcess),
+            KeyCode::Char('r') if self.live => self.open(InputKind::Priority),
+            KeyCode::Char('x') if self.live => return Action::Kill,
+            KeyCode::Char('w') if self.live => return Action::Rewind,
+            KeyCode::Char('m') if self.live => self.open(InputKind::Policy),
+            KeyCode::Char('q') => return Action::Quit,
+            _ => {}
+        }
+        Action::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::Simulation,
+        process::{Burst, BurstKind, Process},
+        scheduler::Policy,
+    };
+
+    #[test]
+    fn test_navigation() {
+        // A runs 0-1 and 3, B arrives at 2 and gets preempted by C arriving at 4 with a better priority.
+        let processes = vec![
+            Process::new("A".to_string(), 0, 2, vec![Burst(BurstKind::Cpu, 2)], 0),
+            Process::new("B".to_string(), 1, 3, vec![Burst(BurstKind::Cpu, 3)], 3),
+            Process::new("C".to_string(), 2, 1, vec![Burst(BurstKind::Cpu, 1)], 4),
+        ];
+        let log = Simulation::with_policy(processes, Policy::Priority).run();
+        let run = RunInfo::of(&log.content);
+        let (len, events) = (run.len, &run.events);
+        let kinds = |kind| events.iter().filter(|e| e.kind == kind).map(|e| e.tick).collect::<Vec<_>>();
+        assert_eq!(kinds(EventKind::Arrival), vec![0, 3, 4]);
+        assert_eq!(kinds(EventKind::Completion), vec![1, 4, 6]);
+        assert_eq!(kinds(EventKind::Preemption), vec![4]);
+        assert_eq!(kinds(EventKind::Idle), vec![2]);
+
+        let mut view = View::new();
+        view.handle_key(KeyCode::Char('p'), &run);
+        assert_eq!(view.time, 5);
+        view.handle_key(KeyCode::Char('A'), &run);
+        assert_eq!(view.time, 4);
+        view.handle_key(KeyCode::End, &run);
+        assert_eq!(view.time, len);
+        // nothing after the end, so it stays put.
+        view.handle_key(KeyCode::Char('c'), &run);
+        assert_eq!(view.time, len);
+
+        for key in [KeyCode::Char('g'), KeyCode::Char('3'), KeyCode::Char('q'), KeyCode::Enter] {
+            assert_eq!(view.handle_key(key, &run), Action::Continue);
+        }
+        assert_eq!((view.time, view.input.clone()), (4, None));
+        view.handle_key(KeyCode::PageUp, &run);
+        assert_eq!(view.time, 1);
+
+        view.handle_key(KeyCode::Char(' '), &run);
+        while view.playing {
+            view.advance(len);
+        }
+        assert_eq!(view.time, len);
+        assert_eq!(view.handle_key(KeyCode::Char('q'), &run), Action::Quit);
+
+        for _ in 0..5 {
+            view.handle_key(KeyCode::Down, &run);
+        }
+        assert_eq!(view.selected, 2);
+
+        // the live keys do nothing until it's live, then going past the end is up to the engine.
+        assert_eq!(view.handle_key(KeyCode::Char('x'), &run), Action::Continue);
+        view.live = true;
+        assert_eq!(view.handle_key(KeyCode::Char('x'), &run), Action::Kill);
+        assert_eq!(view.handle_key(KeyCode::Right, &run), Action::Simulate);
+        for key in [KeyCode::Char('r'), KeyCode::Char('-'), KeyCode::Char('2')] {
+            view.handle_key(key, &run);
+        }
+        assert_eq!(view.handle_key(KeyCode::Enter, &run), Action::SetPriority(-2));
+        view.handle_key(KeyCode::Char('n'), &run);
+        for c in "D 1 4".chars() {
+            view.handle_key(KeyCode::Char(c), &run);
+        }
+        assert_eq!(view.handle_key(KeyCode::Enter, &run), Action::AddProcess("D 1 4".to_string()));
+
+        assert_eq!(view.handle_key(KeyCode::Char('w'), &run), Action::Rewind);
+        view.handle_key(KeyCode::Char('m'), &run);
+        for c in "rr:3".chars() {
+            view.handle_key(KeyCode::Char(c), &run);
+        }
+        assert_eq!(view.handle_key(KeyCode::Enter, &run), Action::SetPolicy(Policy::RoundRobin { quantum: 3 }));
+        view.handle_key(KeyCode::Char('m'), &run);
+        view.handle_key(KeyCode::Char('z'), &run);
+        assert_eq!(view.handle_key(KeyCode::Enter, &run), Action::Continue);
+        assert_eq!(view.message.as_deref(), Some("Couldn't switch scheduler: unknown scheduler \"z\""));
+    }
+}